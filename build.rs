@@ -0,0 +1,17 @@
+use std::env;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(cbc_ge_2_10)");
+    println!("cargo:rustc-check-cfg=cfg(cbc_master)");
+    // Set by the build script of coin_cbc_sys when libCbcSolver >= 2.10
+    // is detected or the `cbc-210` feature is enabled.
+    if env::var_os("DEP_CBCSOLVER_CBC_GE_2_10").is_some() {
+        println!("cargo:rustc-cfg=cbc_ge_2_10");
+    }
+    // Set by the build script of coin_cbc_sys when the C interface of
+    // the CBC master branch is detected or the `cbc-310` feature is
+    // enabled.
+    if env::var_os("DEP_CBCSOLVER_CBC_MASTER").is_some() {
+        println!("cargo:rustc-cfg=cbc_master");
+    }
+}
@@ -1,5 +1,300 @@
 extern crate pkg_config;
 
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// The directory containing libCbcSolver and its dependencies.
+const LIB_DIR_VAR: &str = "COIN_CBC_LIB_DIR";
+/// The directory containing the Cbc headers, used to detect the version.
+const INCLUDE_DIR_VAR: &str = "COIN_CBC_INCLUDE_DIR";
+/// Set to 1 to link the static archives.
+const STATIC_VAR: &str = "COIN_CBC_STATIC";
+/// The directories searched by pkg-config before the default ones.
+const PKG_CONFIG_PATH_VAR: &str = "PKG_CONFIG_PATH";
+
+/// Functions of the C interface of the CBC master branch, missing in
+/// the released versions.
+const MASTER_FUNCTIONS: &[&str] = &[
+    "Cbc_getRowPrice",
+    "Cbc_addIncumbentCallback",
+    "Cbc_addLazyConstraint",
+];
+
+/// The libraries to link statically, each one after the ones using it.
+const STATIC_LIBS: &[&str] = &[
+    "CbcSolver",
+    "Cbc",
+    "Cgl",
+    "OsiClp",
+    "Clp",
+    "Osi",
+    "CoinUtils",
+];
+
 fn main() {
-    let _ = pkg_config::probe_library("cbc");
+    println!("cargo:rustc-check-cfg=cfg(cbc_ge_2_10)");
+    println!("cargo:rustc-check-cfg=cfg(cbc_master)");
+    println!("cargo:rerun-if-changed=build.rs");
+    for var in &[
+        LIB_DIR_VAR,
+        INCLUDE_DIR_VAR,
+        STATIC_VAR,
+        PKG_CONFIG_PATH_VAR,
+    ] {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    // The `cbc-310` feature forces the bindings of the CBC master
+    // branch, and the `cbc-210` feature, which it enables, the ones of
+    // CBC 2.10, when they cannot be detected.
+    let mut is_master = env::var_os("CARGO_FEATURE_CBC_310").is_some();
+    let mut is_ge_2_10 = env::var_os("CARGO_FEATURE_CBC_210").is_some();
+    // With dynamic loading, nothing is linked and all the functions of
+    // CBC 2.10 are bound.
+    if env::var_os("CARGO_FEATURE_DYNAMIC_LOADING").is_some() {
+        emit_cfgs(true, is_master);
+        return Ok(());
+    }
+    let is_static = env::var(STATIC_VAR).as_deref() == Ok("1");
+    let mut include_dirs = vec![];
+    let mut version = match env::var_os(INCLUDE_DIR_VAR) {
+        Some(dir) => {
+            include_dirs.push(PathBuf::from(&dir));
+            Some(header_version(Path::new(&dir))?)
+        }
+        None => None,
+    };
+    match env::var_os(LIB_DIR_VAR) {
+        Some(dir) => link_from_dir(Path::new(&dir), is_static)?,
+        None => {
+            let lib = link_with_pkg_config(is_static)?;
+            version = version.or(Some(lib.version));
+            include_dirs.extend(lib.include_paths);
+        }
+    }
+    if let Some(version) = &version {
+        println!("cargo:version={}", version);
+        is_ge_2_10 |= is_at_least(version, (2, 10));
+    }
+    // The master branch has no released version, so its interface is
+    // detected from the header instead.
+    is_master |= include_dirs.iter().any(|dir| has_master_interface(dir));
+    emit_cfgs(is_ge_2_10 || is_master, is_master);
+    Ok(())
+}
+
+/// Enables the bindings of CBC 2.10 and of the CBC master branch, in
+/// this crate and, through the `DEP_CBCSOLVER_*` variables, in the
+/// crates depending on it.
+fn emit_cfgs(is_ge_2_10: bool, is_master: bool) {
+    if is_ge_2_10 {
+        println!("cargo:rustc-cfg=cbc_ge_2_10");
+        println!("cargo:cbc_ge_2_10=1");
+    }
+    if is_master {
+        println!("cargo:rustc-cfg=cbc_master");
+        println!("cargo:cbc_master=1");
+    }
+}
+
+/// Links the libraries found in `dir`.
+fn link_from_dir(dir: &Path, is_static: bool) -> Result<(), String> {
+    let libs: &[&str] = if is_static {
+        STATIC_LIBS
+    } else {
+        &["CbcSolver"]
+    };
+    let mut searched = vec![];
+    for lib in libs {
+        let candidates = lib_file_names(lib, is_static);
+        if !candidates.iter().any(|name| dir.join(name).is_file()) {
+            searched.extend(candidates.iter().map(|name| dir.join(name)));
+        }
+    }
+    if !searched.is_empty() {
+        return Err(format!(
+            "cannot find the Cbc libraries in {}={}, searched:\n{}",
+            LIB_DIR_VAR,
+            dir.display(),
+            list(&searched)
+        ));
+    }
+    println!("cargo:rustc-link-search=native={}", dir.display());
+    link_libs(libs, is_static);
+    Ok(())
+}
+
+/// Links the libraries given by pkg-config, and returns what it found
+/// about Cbc.
+fn link_with_pkg_config(is_static: bool) -> Result<pkg_config::Library, String> {
+    let lib = pkg_config::Config::new()
+        .statik(is_static)
+        .cargo_metadata(!is_static)
+        .probe("cbc")
+        .map_err(|e| {
+            let pkg_config_path = match env::var(PKG_CONFIG_PATH_VAR) {
+                Ok(path) => format!("is {}", path),
+                Err(_) => "is not set".to_owned(),
+            };
+            format!(
+                "cannot find Cbc: set {} to the directory of libCbcSolver, or add the \
+                 directory of cbc.pc to {}, which {}; pkg-config failed: {}",
+                LIB_DIR_VAR, PKG_CONFIG_PATH_VAR, pkg_config_path, e
+            )
+        })?;
+    if is_static {
+        link_static_with_pkg_config(&lib)?;
+    }
+    Ok(lib)
+}
+
+/// Links the static Cbc libraries, in the directories given by
+/// pkg-config, and their dependencies dynamically. pkg-config itself
+/// would link the libraries of the system directories dynamically, and
+/// not the C++ runtime.
+fn link_static_with_pkg_config(lib: &pkg_config::Library) -> Result<(), String> {
+    let mut dirs = lib.link_paths.clone();
+    if let Ok(libdir) = pkg_config::get_variable("cbc", "libdir") {
+        // Left out by pkg-config when it is a system directory
+        if !dirs.contains(&PathBuf::from(&libdir)) {
+            dirs.push(libdir.into());
+        }
+    }
+    let mut searched = vec![];
+    for name in STATIC_LIBS {
+        let candidates = lib_file_names(name, true);
+        let paths: Vec<PathBuf> = dirs
+            .iter()
+            .flat_map(|dir| candidates.iter().map(move |file| dir.join(file)))
+            .collect();
+        if !paths.iter().any(|path| path.is_file()) {
+            searched.extend(paths);
+        }
+    }
+    if !searched.is_empty() {
+        return Err(format!(
+            "cannot find the static Cbc libraries in the directories given by pkg-config, \
+             searched:\n{}",
+            list(&searched)
+        ));
+    }
+    for dir in &dirs {
+        println!("cargo:rustc-link-search=native={}", dir.display());
+    }
+    link_libs(STATIC_LIBS, true);
+    // The dependencies of Cbc, e.g. lapack or z
+    for name in lib
+        .libs
+        .iter()
+        .filter(|name| !STATIC_LIBS.contains(&name.as_str()))
+    {
+        println!("cargo:rustc-link-lib={}", name);
+    }
+    Ok(())
+}
+
+/// Emits the link directives of the libraries, followed by the C++
+/// runtime when they are static.
+fn link_libs(libs: &[&str], is_static: bool) {
+    let kind = if is_static { "static=" } else { "" };
+    for lib in libs {
+        println!("cargo:rustc-link-lib={}{}", kind, lib);
+    }
+    if is_static {
+        let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+        let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+        match (target_os.as_str(), target_env.as_str()) {
+            (_, "msvc") => {}
+            ("macos", _) | ("ios", _) | ("freebsd", _) | ("openbsd", _) => {
+                println!("cargo:rustc-link-lib=c++")
+            }
+            _ => println!("cargo:rustc-link-lib=stdc++"),
+        }
+    }
+}
+
+/// The possible file names of a library.
+fn lib_file_names(lib: &str, is_static: bool) -> Vec<String> {
+    if is_static {
+        vec![format!("lib{}.a", lib), format!("{}.lib", lib)]
+    } else {
+        vec![
+            format!("lib{}.so", lib),
+            format!("lib{}.dylib", lib),
+            format!("{}.lib", lib),
+            format!("lib{}.dll.a", lib),
+        ]
+    }
+}
+
+/// Reads the Cbc version from the configuration headers in `dir`.
+fn header_version(dir: &Path) -> Result<String, String> {
+    let mut searched = vec![];
+    for subdir in &["coin", "coin-or", ""] {
+        for header in &["CbcConfig.h", "config_cbc_default.h"] {
+            let path = dir.join(subdir).join(header);
+            if let Ok(contents) = fs::read_to_string(&path) {
+                if let Some(version) = defined_version(&contents) {
+                    return Ok(version);
+                }
+            }
+            searched.push(path);
+        }
+    }
+    Err(format!(
+        "cannot find the Cbc version in {}={}, searched for CBC_VERSION in:\n{}",
+        INCLUDE_DIR_VAR,
+        dir.display(),
+        list(&searched)
+    ))
+}
+
+/// Checks whether the C interface header in `dir` declares the
+/// functions of the CBC master branch.
+fn has_master_interface(dir: &Path) -> bool {
+    ["coin", "coin-or", ""].iter().any(|subdir| {
+        let path = dir.join(subdir).join("Cbc_C_Interface.h");
+        fs::read_to_string(path)
+            .is_ok_and(|header| MASTER_FUNCTIONS.iter().all(|f| header.contains(f)))
+    })
+}
+
+/// Gets the value of `#define CBC_VERSION "x.y.z"`.
+fn defined_version(header: &str) -> Option<String> {
+    header.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (Some("#define"), Some("CBC_VERSION"), Some(value)) => {
+                Some(value.trim_matches('"').to_owned())
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Formats one path per line.
+fn list(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("  {}", path.display()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Checks that a `major.minor[.patch]` version is at least `min`.
+fn is_at_least(version: &str, min: (u32, u32)) -> bool {
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|part| part.parse::<u32>().unwrap_or(0));
+    let major = parts.next().unwrap_or(0);
+    let minor = parts.next().unwrap_or(0);
+    (major, minor) >= min
 }
@@ -0,0 +1,75 @@
+//! Runtime loading of libCbcSolver.
+
+use libloading::Library;
+use std::ffi::OsString;
+use std::sync::OnceLock;
+
+/// The environment variable giving the path of the library to load.
+const LIBRARY_PATH_VAR: &str = "COIN_CBC_LIBRARY";
+
+/// The versioned file names of libCbcSolver, tried after the
+/// unversioned one, which is only installed with the headers.
+#[cfg(target_os = "macos")]
+const VERSIONED_NAMES: &[&str] = &["libCbcSolver.3.dylib", "libCbcSolver.0.dylib"];
+#[cfg(windows)]
+const VERSIONED_NAMES: &[&str] = &["libCbcSolver-3.dll", "libCbcSolver-0.dll"];
+#[cfg(not(any(target_os = "macos", windows)))]
+const VERSIONED_NAMES: &[&str] = &["libCbcSolver.so.3", "libCbcSolver.so.0"];
+
+/// Loads libCbcSolver once, from the path in the `COIN_CBC_LIBRARY`
+/// environment variable if set, or else by its names in the library
+/// search path.
+fn library() -> Result<&'static Library, &'static str> {
+    static LIBRARY: OnceLock<Result<Library, String>> = OnceLock::new();
+    LIBRARY
+        .get_or_init(|| {
+            let names = match std::env::var_os(LIBRARY_PATH_VAR) {
+                Some(path) => vec![path],
+                None => Some(libloading::library_filename("CbcSolver"))
+                    .into_iter()
+                    .chain(VERSIONED_NAMES.iter().map(OsString::from))
+                    .collect(),
+            };
+            let mut errors = vec![];
+            for name in &names {
+                match unsafe { Library::new(name) } {
+                    Ok(library) => return Ok(library),
+                    Err(e) => errors.push(format!("  {}: {}", name.to_string_lossy(), e)),
+                }
+            }
+            Err(format!(
+                "cannot load libCbcSolver, set {} to its path; tried:\n{}",
+                LIBRARY_PATH_VAR,
+                errors.join("\n")
+            ))
+        })
+        .as_ref()
+        .map_err(|e| e.as_str())
+}
+
+/// Loads libCbcSolver if it is not loaded yet. This is done by the
+/// first call to a function, but calling it first allows to report
+/// a loading failure as an error instead of a panic.
+pub fn load() -> Result<(), String> {
+    library().map(|_| ()).map_err(str::to_owned)
+}
+
+/// Checks that libCbcSolver is loaded and provides the function
+/// `name`, e.g. `"Cbc_getRowActivity"`.
+pub fn is_available(name: &str) -> bool {
+    let mut symbol = name.as_bytes().to_vec();
+    symbol.push(0);
+    library().is_ok_and(|lib| unsafe { lib.get::<unsafe extern "C" fn()>(&symbol) }.is_ok())
+}
+
+/// Resolves a function of libCbcSolver; `name` must end with a nul
+/// byte. Panics if the library cannot be loaded.
+pub(crate) fn symbol<T: Copy>(name: &[u8]) -> Option<T> {
+    let lib = library().unwrap_or_else(|e| panic!("{}", e));
+    unsafe { lib.get::<T>(name) }.ok().map(|symbol| *symbol)
+}
+
+/// Reports a call to a function missing in the loaded libCbcSolver.
+pub(crate) fn unavailable(name: &str) -> ! {
+    panic!("function {} unavailable in this libcbc", name)
+}
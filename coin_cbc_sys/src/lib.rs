@@ -1,9 +1,63 @@
 //! Raw coin cbc bindings for CBC 2.9. For documentation see official
 //! documentation.
+//!
+//! The entry points added in CBC 2.10 are available when pkg-config
+//! reports cbc >= 2.10, or with the `cbc-210` feature, which requires
+//! libCbcSolver >= 2.10. The ones of the CBC master branch, missing in
+//! the released versions, are available when its Cbc_C_Interface.h
+//! declares them, or with the `cbc-310` feature.
+//!
+//! With the `dynamic-loading` feature, libCbcSolver is loaded at runtime
+//! instead of being linked, and the entry points of CBC 2.10 are
+//! available, as well as the ones of the master branch with the
+//! `cbc-310` feature; the ones missing in the loaded library panic when
+//! called.
 #![allow(non_camel_case_types)]
 
+#[cfg(feature = "dynamic-loading")]
+mod dynamic;
+#[cfg(feature = "dynamic-loading")]
+pub use dynamic::{is_available, load};
+
+#[cfg(cbc_master)]
+use std::os::raw::c_void;
 use std::os::raw::{c_char, c_int};
 
+/// Declares the functions of libCbcSolver, either linked or, with the
+/// `dynamic-loading` feature, resolved at runtime.
+#[cfg(not(feature = "dynamic-loading"))]
+macro_rules! cbc_functions {
+    ($($(#[$attr:meta])* pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        // Linked by the build script
+        extern "C" {
+            $($(#[$attr])* pub fn $name($($arg: $ty),*) $(-> $ret)?;)*
+        }
+    };
+}
+
+/// Declares the functions of libCbcSolver, either linked or, with the
+/// `dynamic-loading` feature, resolved at runtime.
+#[cfg(feature = "dynamic-loading")]
+macro_rules! cbc_functions {
+    ($($(#[$attr:meta])* pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        $(
+            $(#[$attr])*
+            ///
+            /// Panics if the function is not available in the loaded libCbcSolver.
+            #[allow(non_snake_case, clippy::missing_safety_doc, clippy::too_many_arguments)]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                type Function = unsafe extern "C" fn($($ty),*) $(-> $ret)?;
+                static FUNCTION: std::sync::OnceLock<Option<Function>> = std::sync::OnceLock::new();
+                let name = concat!(stringify!($name), "\0");
+                match *FUNCTION.get_or_init(|| dynamic::symbol(name.as_bytes())) {
+                    Some(function) => function($($arg),*),
+                    None => dynamic::unavailable(stringify!($name)),
+                }
+            }
+        )*
+    };
+}
+
 #[repr(C)]
 pub struct Cbc_Model {
     _private: [u8; 0],
@@ -21,9 +75,30 @@ pub type cbc_callback = Option<
         cvec: *mut *mut c_char,
     ),
 >;
+/// Cut generator callback: gets the `OsiSolverInterface` of the
+/// current node, the `OsiCuts` to add cuts to, and the user data given
+/// to `Cbc_addCutCallback`.
+#[cfg(cbc_master)]
+pub type cbc_cut_callback = Option<
+    unsafe extern "C" fn(osi_solver: *mut c_void, osi_cuts: *mut c_void, app_data: *mut c_void),
+>;
+/// Incumbent callback: gets the `CbcModel`, the objective value and
+/// the non-zero columns of the new solution, by name, and the user data
+/// given to `Cbc_addIncumbentCallback`. Returns whether the solution is
+/// accepted.
+#[cfg(cbc_master)]
+pub type cbc_incumbent_callback = Option<
+    unsafe extern "C" fn(
+        cbc_model: *mut c_void,
+        obj: f64,
+        nz: c_int,
+        vnames: *mut *mut c_char,
+        x: *mut f64,
+        app_data: *mut c_void,
+    ) -> c_int,
+>;
 
-#[link(name = "CbcSolver")]
-extern "C" {
+cbc_functions! {
     pub fn Cbc_newModel() -> *mut Cbc_Model;
     pub fn Cbc_deleteModel(model: *mut Cbc_Model);
     pub fn Cbc_getVersion() -> *const c_char;
@@ -108,10 +183,6 @@ extern "C" {
     pub fn Cbc_getRowActivity(model: *mut Cbc_Model) -> *const f64;
     /// Primal column solution
     pub fn Cbc_getColSolution(model: *mut Cbc_Model) -> *const f64;
-    /* Disabled until available in the C API: https://github.com/coin-or/Cbc/issues/370
-    /// Dual row solution
-    pub fn Cbc_getRowPrice(model: *mut Cbc_Model) -> *const f64;
-     */
     /// Dual column solution
     pub fn Cbc_getReducedCost(model: *mut Cbc_Model) -> *const f64;
     pub fn Cbc_getObjValue(model: *mut Cbc_Model) -> f64;
@@ -122,7 +193,124 @@ extern "C" {
     pub fn Cbc_secondaryStatus(model: *mut Cbc_Model) -> c_int;
 }
 
+// Entry points added in CBC 2.10.
+#[cfg(cbc_ge_2_10)]
+cbc_functions! {
+    pub fn Cbc_readLp(model: *mut Cbc_Model, filename: *const c_char) -> c_int;
+    pub fn Cbc_writeLp(model: *mut Cbc_Model, filename: *const c_char);
+    pub fn Cbc_addCol(
+        model: *mut Cbc_Model,
+        name: *const c_char,
+        lb: f64,
+        ub: f64,
+        obj: f64,
+        isInteger: c_char,
+        nz: c_int,
+        rows: *mut c_int,
+        coefs: *mut f64,
+    );
+    pub fn Cbc_addRow(
+        model: *mut Cbc_Model,
+        name: *const c_char,
+        nz: c_int,
+        cols: *const c_int,
+        coefs: *const f64,
+        sense: c_char,
+        rhs: f64,
+    );
+    pub fn Cbc_deleteRows(model: *mut Cbc_Model, numRows: c_int, rows: *const c_int);
+    pub fn Cbc_deleteCols(model: *mut Cbc_Model, numCols: c_int, cols: *const c_int);
+    pub fn Cbc_getRowNz(model: *mut Cbc_Model, row: c_int) -> c_int;
+    pub fn Cbc_getRowIndices(model: *mut Cbc_Model, row: c_int) -> *const c_int;
+    pub fn Cbc_getRowCoeffs(model: *mut Cbc_Model, row: c_int) -> *const f64;
+    pub fn Cbc_getRowRHS(model: *mut Cbc_Model, row: c_int) -> f64;
+    pub fn Cbc_getRowSense(model: *mut Cbc_Model, row: c_int) -> c_char;
+    pub fn Cbc_getColNz(model: *mut Cbc_Model, col: c_int) -> c_int;
+    pub fn Cbc_getColIndices(model: *mut Cbc_Model, col: c_int) -> *const c_int;
+    pub fn Cbc_getColCoeffs(model: *mut Cbc_Model, col: c_int) -> *const f64;
+    pub fn Cbc_getNumIntegers(model: *mut Cbc_Model) -> c_int;
+    pub fn Cbc_setMaximumSeconds(model: *mut Cbc_Model, maxSeconds: f64);
+    pub fn Cbc_getMaximumSeconds(model: *mut Cbc_Model) -> f64;
+    pub fn Cbc_setMaximumNodes(model: *mut Cbc_Model, maxNodes: c_int);
+    pub fn Cbc_getMaximumNodes(model: *mut Cbc_Model) -> c_int;
+    pub fn Cbc_setMaximumSolutions(model: *mut Cbc_Model, maxSolutions: c_int);
+    pub fn Cbc_getMaximumSolutions(model: *mut Cbc_Model) -> c_int;
+    pub fn Cbc_setLogLevel(model: *mut Cbc_Model, logLevel: c_int);
+    pub fn Cbc_getLogLevel(model: *mut Cbc_Model) -> c_int;
+    pub fn Cbc_setCutoff(model: *mut Cbc_Model, cutoff: f64);
+    pub fn Cbc_getCutoff(model: *mut Cbc_Model) -> f64;
+    pub fn Cbc_setAllowableGap(model: *mut Cbc_Model, allowedGap: f64);
+    pub fn Cbc_getAllowableGap(model: *mut Cbc_Model) -> f64;
+    pub fn Cbc_setAllowableFractionGap(model: *mut Cbc_Model, allowedFracionGap: f64);
+    pub fn Cbc_getAllowableFractionGap(model: *mut Cbc_Model) -> f64;
+    pub fn Cbc_setAllowablePercentageGap(model: *mut Cbc_Model, allowedPercentageGap: f64);
+    pub fn Cbc_getAllowablePercentageGap(model: *mut Cbc_Model) -> f64;
+    pub fn Cbc_numberSavedSolutions(model: *mut Cbc_Model) -> c_int;
+    pub fn Cbc_savedSolution(model: *mut Cbc_Model, whichSol: c_int) -> *const f64;
+    pub fn Cbc_savedSolutionObj(model: *mut Cbc_Model, whichSol: c_int) -> f64;
+    /// Sets a possibly partial MIP start
+    pub fn Cbc_setMIPStart(
+        model: *mut Cbc_Model,
+        count: c_int,
+        colNames: *const *const c_char,
+        colValues: *const f64,
+    );
+    /// Sets a possibly partial MIP start by column index
+    pub fn Cbc_setMIPStartI(
+        model: *mut Cbc_Model,
+        count: c_int,
+        colIdxs: *const c_int,
+        colValues: *const f64,
+    );
+}
+
+// Entry points of the CBC master branch, missing in the C interface of
+// the released versions: https://github.com/coin-or/Cbc/issues/370
+#[cfg(cbc_master)]
+cbc_functions! {
+    /// Dual row solution
+    pub fn Cbc_getRowPrice(model: *mut Cbc_Model) -> *const f64;
+    /// Adds a cut generator callback
+    pub fn Cbc_addCutCallback(
+        model: *mut Cbc_Model,
+        cutcb: cbc_cut_callback,
+        name: *const c_char,
+        appData: *mut c_void,
+        howOften: c_int,
+        atSolution: c_char,
+    );
+    /// Sets the incumbent callback
+    pub fn Cbc_addIncumbentCallback(
+        model: *mut Cbc_Model,
+        inccb: cbc_incumbent_callback,
+        appData: *mut c_void,
+    );
+    /// Adds a constraint that is only checked on integer solutions
+    pub fn Cbc_addLazyConstraint(
+        model: *mut Cbc_Model,
+        nz: c_int,
+        idx: *mut c_int,
+        coef: *mut f64,
+        sense: c_char,
+        rhs: f64,
+    );
+    /// Number of columns of an `OsiSolverInterface`
+    pub fn Osi_getNumCols(osi: *mut c_void) -> c_int;
+    /// Column solution of an `OsiSolverInterface`
+    pub fn Osi_getColSolution(osi: *mut c_void) -> *const f64;
+    /// Adds a row cut to an `OsiCuts`
+    pub fn OsiCuts_addRowCut(
+        osiCuts: *mut c_void,
+        nz: c_int,
+        idx: *const c_int,
+        coef: *const f64,
+        sense: c_char,
+        rhs: f64,
+    );
+}
+
 #[cfg(test)]
+#[allow(clippy::legacy_numeric_constants)]
 mod tests {
     use super::*;
 
@@ -183,4 +371,251 @@ mod tests {
             Cbc_deleteModel(model);
         }
     }
+
+    /// Calls each entry point added in CBC 2.10.
+    #[cfg(cbc_ge_2_10)]
+    #[test]
+    fn cbc_2_10_interface() {
+        use std::ffi::CString;
+        use std::slice::from_raw_parts;
+
+        // The knapsack problem above, as a minimization, built column
+        // by column
+        let weights = [2., 8., 4., 2., 5.];
+        let obj = [-5., -3., -2., -7., -4.];
+        let names: Vec<CString> = (0..5)
+            .map(|i| CString::new(format!("x{}", i)).unwrap())
+            .collect();
+        let path = std::env::temp_dir().join(format!("coin_cbc_sys_{}.lp", std::process::id()));
+        let lp_file = CString::new(path.to_str().unwrap()).unwrap();
+
+        unsafe {
+            let model = Cbc_newModel();
+            Cbc_addRow(
+                model,
+                b"capacity\0".as_ptr() as *const c_char,
+                0,
+                std::ptr::null(),
+                std::ptr::null(),
+                b'L' as c_char,
+                10.,
+            );
+            for i in 0..5 {
+                let mut rows = [0];
+                let mut coefs = [weights[i]];
+                Cbc_addCol(
+                    model,
+                    names[i].as_ptr(),
+                    0.,
+                    1.,
+                    obj[i],
+                    1,
+                    1,
+                    rows.as_mut_ptr(),
+                    coefs.as_mut_ptr(),
+                );
+            }
+            assert_eq!(5, Cbc_getNumIntegers(model));
+            assert_eq!(5, Cbc_getRowNz(model, 0));
+            assert_eq!(
+                &[0, 1, 2, 3, 4],
+                from_raw_parts(Cbc_getRowIndices(model, 0), 5)
+            );
+            assert_eq!(&weights, from_raw_parts(Cbc_getRowCoeffs(model, 0), 5));
+            assert_eq!(10., Cbc_getRowRHS(model, 0));
+            assert_eq!(b'L' as c_char, Cbc_getRowSense(model, 0));
+            assert_eq!(1, Cbc_getColNz(model, 1));
+            assert_eq!(&[0], from_raw_parts(Cbc_getColIndices(model, 1), 1));
+            assert_eq!(&[8.], from_raw_parts(Cbc_getColCoeffs(model, 1), 1));
+
+            // A row and a column to delete
+            Cbc_addRow(
+                model,
+                b"extra\0".as_ptr() as *const c_char,
+                2,
+                [0, 1].as_ptr(),
+                [1., 1.].as_ptr(),
+                b'E' as c_char,
+                2.,
+            );
+            Cbc_addCol(
+                model,
+                b"y\0".as_ptr() as *const c_char,
+                0.,
+                1.,
+                0.,
+                0,
+                0,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            Cbc_deleteRows(model, 1, [1].as_ptr());
+            Cbc_deleteCols(model, 1, [5].as_ptr());
+            assert_eq!(1, Cbc_getNumRows(model));
+            assert_eq!(5, Cbc_getNumCols(model));
+
+            Cbc_writeLp(model, lp_file.as_ptr());
+            let read = Cbc_newModel();
+            Cbc_readLp(read, lp_file.as_ptr());
+            let _ = std::fs::remove_file(&path);
+            assert_eq!(5, Cbc_getNumCols(read));
+            assert_eq!(1, Cbc_getNumRows(read));
+            Cbc_deleteModel(read);
+
+            Cbc_setLogLevel(model, 0);
+            assert_eq!(0, Cbc_getLogLevel(model));
+            Cbc_setMaximumSeconds(model, 60.);
+            assert_eq!(60., Cbc_getMaximumSeconds(model));
+            Cbc_setMaximumNodes(model, 1000);
+            assert_eq!(1000, Cbc_getMaximumNodes(model));
+            Cbc_setMaximumSolutions(model, 10);
+            assert_eq!(10, Cbc_getMaximumSolutions(model));
+            Cbc_setCutoff(model, 0.);
+            assert_eq!(0., Cbc_getCutoff(model));
+            Cbc_setAllowableGap(model, 0.);
+            assert_eq!(0., Cbc_getAllowableGap(model));
+            Cbc_setAllowableFractionGap(model, 0.);
+            assert_eq!(0., Cbc_getAllowableFractionGap(model));
+            Cbc_setAllowablePercentageGap(model, 0.);
+            assert_eq!(0., Cbc_getAllowablePercentageGap(model));
+
+            let start = [names[0].as_ptr(), names[3].as_ptr()];
+            Cbc_setMIPStart(model, 2, start.as_ptr(), [1., 1.].as_ptr());
+            Cbc_setMIPStartI(model, 3, [0, 3, 4].as_ptr(), [1., 1., 1.].as_ptr());
+
+            Cbc_solve(model);
+
+            assert!(Cbc_isProvenOptimal(model) != 0);
+            assert!((Cbc_getObjValue(model) + 16.).abs() < 1e-6);
+            assert!(Cbc_numberSavedSolutions(model) >= 1);
+            assert!((Cbc_savedSolutionObj(model, 0) + 16.).abs() < 1e-6);
+            assert_eq!(
+                from_raw_parts(Cbc_getColSolution(model), 5),
+                from_raw_parts(Cbc_savedSolution(model, 0), 5)
+            );
+
+            Cbc_deleteModel(model);
+        }
+    }
+
+    /// Calls each entry point of the CBC master branch.
+    #[cfg(cbc_master)]
+    #[test]
+    fn cbc_master_interface() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        // At most 2 items
+        unsafe extern "C" fn at_most_2(
+            osi_solver: *mut c_void,
+            osi_cuts: *mut c_void,
+            app_data: *mut c_void,
+        ) {
+            (*(app_data as *const AtomicBool)).store(true, Ordering::Relaxed);
+            let num_cols = Osi_getNumCols(osi_solver) as usize;
+            let sol = std::slice::from_raw_parts(Osi_getColSolution(osi_solver), num_cols);
+            if sol.iter().sum::<f64>() > 2. + 1e-6 {
+                let indices = [0, 1, 2, 3, 4];
+                let coefs = [1.; 5];
+                let sense = b'L' as c_char;
+                OsiCuts_addRowCut(osi_cuts, 5, indices.as_ptr(), coefs.as_ptr(), sense, 2.);
+            }
+        }
+        unsafe extern "C" fn last_obj(
+            _cbc_model: *mut c_void,
+            obj: f64,
+            _nz: c_int,
+            _names: *mut *mut c_char,
+            _x: *mut f64,
+            app_data: *mut c_void,
+        ) -> c_int {
+            *(app_data as *mut f64) = obj;
+            1
+        }
+
+        // The knapsack problem above, as a minimization
+        let start = [0, 1, 2, 3, 4, 5];
+        let rowindex = [0, 0, 0, 0, 0];
+        let value = [2., 8., 4., 2., 5.];
+        let collb = [0., 0., 0., 0., 0.];
+        let colub = [1., 1., 1., 1., 1.];
+        let obj = [-5., -3., -2., -7., -4.];
+        let rowlb = [-f64::INFINITY];
+        let rowub = [10.];
+        let called = AtomicBool::new(false);
+        let mut incumbent_obj: f64 = 0.;
+
+        unsafe {
+            let model = Cbc_newModel();
+            Cbc_loadProblem(
+                model,
+                5,
+                1,
+                start.as_ptr(),
+                rowindex.as_ptr(),
+                value.as_ptr(),
+                collb.as_ptr(),
+                colub.as_ptr(),
+                obj.as_ptr(),
+                rowlb.as_ptr(),
+                rowub.as_ptr(),
+            );
+            for i in 0..5 {
+                Cbc_setInteger(model, i);
+            }
+            Cbc_setParameter(
+                model,
+                b"log\0".as_ptr() as *const c_char,
+                b"0\0".as_ptr() as *const c_char,
+            );
+            Cbc_setParameter(
+                model,
+                b"preprocess\0".as_ptr() as *const c_char,
+                b"off\0".as_ptr() as *const c_char,
+            );
+            Cbc_addCutCallback(
+                model,
+                Some(at_most_2),
+                b"at most 2\0".as_ptr() as *const c_char,
+                &called as *const AtomicBool as *mut c_void,
+                1,
+                1,
+            );
+            Cbc_addIncumbentCallback(
+                model,
+                Some(last_obj),
+                &mut incumbent_obj as *mut f64 as *mut c_void,
+            );
+            // Items 0 and 3 exclude each other
+            let mut indices = [0, 3];
+            let mut coefs = [1., 1.];
+            Cbc_addLazyConstraint(
+                model,
+                2,
+                indices.as_mut_ptr(),
+                coefs.as_mut_ptr(),
+                b'L' as c_char,
+                1.,
+            );
+
+            Cbc_solve(model);
+
+            assert!(Cbc_isProvenOptimal(model) != 0);
+            // Items 3 and 4
+            assert!((Cbc_getObjValue(model) + 11.).abs() < 1e-6);
+            assert!(called.load(Ordering::Relaxed));
+            assert!((incumbent_obj + 11.).abs() < 1e-6);
+            let row_price = std::slice::from_raw_parts(Cbc_getRowPrice(model), 1);
+            assert!(row_price[0].is_finite());
+
+            Cbc_deleteModel(model);
+        }
+    }
+
+    #[cfg(feature = "dynamic-loading")]
+    #[test]
+    fn dynamic_loading() {
+        assert_eq!(Ok(()), load());
+        assert!(is_available("Cbc_newModel"));
+        assert!(!is_available("Cbc_notAFunction"));
+    }
 }
@@ -0,0 +1,119 @@
+//! Branching priorities and directions.
+
+use crate::raw::{self, BranchDirection};
+use crate::{Col, Model};
+
+/// The priority of the columns without a branching priority.
+const DEFAULT_BRANCH_PRIORITY: i32 = 1000;
+
+impl Model {
+    /// Sets the branching priority of the given integer column: lower
+    /// priorities are branched on first. The default priority is 1000.
+    pub fn set_branch_priority(&mut self, col: Col, priority: i32) {
+        self.branch_priorities.insert(col, priority);
+    }
+    /// Gets the branching priority of the given column, if set.
+    pub fn branch_priority(&self, col: Col) -> Option<i32> {
+        self.branch_priorities.get(&col).copied()
+    }
+    /// Sets the branch to explore first when branching on the given
+    /// integer column.
+    pub fn set_branch_direction(&mut self, col: Col, direction: BranchDirection) {
+        self.branch_directions.insert(col, direction);
+    }
+    /// Gets the preferred branching direction of the given column, if set.
+    pub fn branch_direction(&self, col: Col) -> Option<BranchDirection> {
+        self.branch_directions.get(&col).copied()
+    }
+    /// Gives the branching priorities and directions to the `raw::Model`.
+    /// On error, e.g. if their file cannot be written, the `raw::Model`
+    /// is left without them.
+    pub(crate) fn add_branching_to_raw(&self, raw: &mut raw::Model) -> Result<(), String> {
+        if self.branch_priorities.is_empty() && self.branch_directions.is_empty() {
+            return Ok(());
+        }
+        let priorities: Vec<_> = self
+            .cols()
+            .filter(|col| {
+                self.branch_priorities.contains_key(col) || self.branch_directions.contains_key(col)
+            })
+            .map(|col| {
+                let priority = self.branch_priority(col).unwrap_or(DEFAULT_BRANCH_PRIORITY);
+                (col.as_usize(), priority, self.branch_direction(col))
+            })
+            .collect();
+        raw.set_branch_priorities(&priorities)
+            .map_err(|e| format!("cannot write the branching priorities file: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ConstraintSense, Sense};
+
+    #[test]
+    fn priorities() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let assign: Vec<Col> = (0..3).map(|_| m.add_binary()).collect();
+        let count = m.add_integer();
+        m.set_col_upper(count, 5.);
+        for &col in &assign {
+            m.set_branch_priority(col, 1);
+            m.set_branch_direction(col, BranchDirection::Up);
+        }
+        m.set_branch_direction(count, BranchDirection::Down);
+        assert_eq!(Some(1), m.branch_priority(assign[0]));
+        assert_eq!(None, m.branch_priority(count));
+        assert_eq!(Some(BranchDirection::Down), m.branch_direction(count));
+
+        // The priorities do not change the optimum
+        m.add_constraint(
+            assign.iter().map(|&c| (c, 2.)).chain(Some((count, 3.))),
+            ConstraintSense::LessOrEqual,
+            7.5,
+        );
+        for &col in assign.iter().chain(Some(&count)) {
+            m.set_obj_coeff(col, 1.);
+        }
+        m.set_obj_sense(Sense::Maximize);
+        let sol = m.solve();
+        assert!(sol.skipped().is_empty());
+        assert!(sol.raw().is_proven_optimal());
+        assert_eq!(3., sol.raw().obj_value());
+    }
+
+    #[test]
+    fn direction() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        // With neither strong branching nor heuristics, and a single
+        // solution allowed, CBC stops at the first leaf of the search:
+        // maximize 3 y + 2 x s.t. 2 y + x <= 2.5, relaxed at y = 0.75
+        for (name, value) in &[
+            ("cuts", "off"),
+            ("heuristicsOnOff", "off"),
+            ("preprocess", "off"),
+            ("strongBranching", "0"),
+            ("trustPseudoCosts", "0"),
+            ("maxSolutions", "1"),
+        ] {
+            m.set_parameter(name, value);
+        }
+        let y = m.add_binary();
+        let x = m.add_col();
+        m.set_col_upper(x, 1.);
+        m.add_constraint(vec![(y, 2.), (x, 1.)], ConstraintSense::LessOrEqual, 2.5);
+        m.set_obj_coeff(y, 3.);
+        m.set_obj_coeff(x, 2.);
+        m.set_obj_sense(Sense::Maximize);
+
+        // y = 0, x = 1
+        m.set_branch_direction(y, BranchDirection::Down);
+        assert_eq!(2., m.solve().raw().obj_value());
+        // y = 1, x = 0.5
+        m.set_branch_direction(y, BranchDirection::Up);
+        assert_eq!(4., m.solve().raw().obj_value());
+    }
+}
@@ -0,0 +1,169 @@
+//! User cut generators called during branch-and-cut.
+
+use crate::{ConstraintSense, Cut, Model};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+use std::sync::{Arc, Mutex};
+
+/// A generator of cuts, called by CBC on the LP solution of the nodes
+/// of the branch-and-cut, and on the integer solutions that it finds.
+///
+/// As it is also called on integer solutions, a generator can enforce
+/// lazy constraints: constraints too numerous to be added upfront,
+/// which are added when a solution violates them.
+pub trait CutGenerator {
+    /// Adds to `sink` the cuts violated by `lp_solution`, the values
+    /// of the columns of the model.
+    ///
+    /// This is called while solving: it must not solve a model itself.
+    /// If it panics, the generator is not called anymore, and the panic
+    /// is resumed once CBC returns. The cuts on a column that is not in
+    /// the model are dropped.
+    fn generate(&mut self, lp_solution: &[f64], sink: &mut CutSink);
+}
+
+impl<F: FnMut(&[f64], &mut CutSink)> CutGenerator for F {
+    fn generate(&mut self, lp_solution: &[f64], sink: &mut CutSink) {
+        self(lp_solution, sink)
+    }
+}
+
+/// The cuts found by a `CutGenerator`.
+#[derive(Debug, Default)]
+pub struct CutSink {
+    cuts: Vec<Cut>,
+}
+
+impl CutSink {
+    /// Adds a cut.
+    pub fn add(&mut self, cut: Cut) {
+        self.cuts.push(cut);
+    }
+    /// Gets the cuts added so far.
+    pub fn cuts(&self) -> &[Cut] {
+        &self.cuts
+    }
+}
+
+/// A cut generator registered on a `Model`.
+#[derive(Clone)]
+pub(crate) struct RegisteredCutGenerator {
+    name: CString,
+    generator: Arc<Mutex<dyn CutGenerator + Send>>,
+}
+
+impl Model {
+    /// Registers a cut generator, called at every node and on every
+    /// integer solution found while solving.
+    ///
+    /// The clones of the model share the generator. As the columns
+    /// seen by the generator must be the ones of the model, CBC
+    /// preprocessing is disabled unless the `preprocess` parameter is
+    /// set.
+    ///
+    /// With the `dynamic-loading` feature, the generators are skipped
+    /// if the loaded libcbc lacks the C interface of the CBC master
+    /// branch, see `Solution::skipped`.
+    pub fn add_cut_generator<G>(&mut self, name: &str, generator: G)
+    where
+        G: CutGenerator + Send + 'static,
+    {
+        self.cut_generators.push(RegisteredCutGenerator {
+            name: CString::new(name).unwrap_or_default(),
+            generator: Arc::new(Mutex::new(generator)),
+        });
+    }
+    /// Registers the cut generators on the `raw::Model`, unless the
+    /// loaded libcbc cannot call them.
+    pub(crate) fn add_cut_generators_to_raw(
+        &self,
+        raw: &mut crate::raw::Model,
+    ) -> Result<(), String> {
+        if self.cut_generators.is_empty() {
+            return Ok(());
+        }
+        // The Cbc_addCutCallback of CBC 2.10 takes fewer arguments: the
+        // one of the master branch comes with Cbc_addLazyConstraint.
+        if !crate::has_cbc_function("Cbc_addLazyConstraint") {
+            return Err(
+                "the cut generators need the C interface of the CBC master branch".to_owned(),
+            );
+        }
+        if !self
+            .parameters
+            .keys()
+            .any(|k| k.as_bytes() == b"preprocess")
+        {
+            let key = CString::new("preprocess").unwrap();
+            raw.set_parameter(&key, &CString::new("off").unwrap());
+        }
+        let num_cols = self.num_cols as usize;
+        for registered in &self.cut_generators {
+            let generator = registered.generator.clone();
+            let callback = move |col_solution: &[f64], row_cuts: &mut crate::raw::RowCuts| {
+                if col_solution.len() < num_cols {
+                    return;
+                }
+                let mut sink = CutSink::default();
+                let mut generator = generator.lock().unwrap_or_else(|e| e.into_inner());
+                generator.generate(&col_solution[..num_cols], &mut sink);
+                for cut in sink.cuts {
+                    let (indices, elements): (Vec<c_int>, Vec<f64>) = cut
+                        .expr()
+                        .iter()
+                        .map(|&(col, coeff)| (col.0 as c_int, coeff))
+                        .unzip();
+                    let sense = match cut.sense() {
+                        ConstraintSense::LessOrEqual => b'L',
+                        ConstraintSense::GreaterOrEqual => b'G',
+                        ConstraintSense::Equal => b'E',
+                    };
+                    // Dropped if on a column that is not in the model
+                    let _ = row_cuts.add_row_cut(&indices, &elements, sense as c_char, cut.rhs());
+                }
+            };
+            raw.add_cut_callback(callback, &registered.name, 1, true);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::knapsack_model;
+    use crate::{Col, Sense};
+
+    #[test]
+    fn lazy_constraints() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let cols: Vec<Col> = (0..4).map(|_| m.add_binary()).collect();
+        for &col in &cols {
+            m.set_obj_coeff(col, 1.);
+        }
+        m.set_obj_sense(Sense::Maximize);
+        // At most 2 of the columns, only known by the generator
+        let all = cols.clone();
+        m.add_cut_generator("at most 2", move |values: &[f64], sink: &mut CutSink| {
+            let sum: f64 = all.iter().map(|c| values[c.as_usize()]).sum();
+            if sum > 2. + 1e-6 {
+                let expr = all.iter().map(|&c| (c, 1.));
+                sink.add(Cut::new(expr, ConstraintSense::LessOrEqual, 2.));
+            }
+        });
+        let sol = m.solve();
+        assert!(sol.raw().is_proven_optimal());
+        assert_eq!(2., cols.iter().map(|&c| sol.col(c)).sum::<f64>());
+    }
+
+    #[test]
+    #[should_panic(expected = "no cut today")]
+    fn panicking_generator() {
+        let (mut m, _) = knapsack_model();
+        m.add_cut_generator("panicking", |_: &[f64], _: &mut CutSink| {
+            panic!("no cut today")
+        });
+        m.solve();
+    }
+}
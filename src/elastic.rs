@@ -0,0 +1,128 @@
+//! Elastic reformulation of rows, for infeasibility repair.
+
+use crate::{Col, Model, Row, Sense, Solution};
+use std::collections::BTreeMap;
+
+/// Tolerance under which an elastic column is considered unused.
+const ELASTIC_TOLERANCE: f64 = 1e-6;
+
+/// The elastic columns added by `Model::elastic`.
+///
+/// The rows and columns of the original model keep their identifiers
+/// in the elastic model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ElasticMap {
+    /// For each elastic row, the columns added to reach its lower
+    /// bound and to stay under its upper bound.
+    slacks: BTreeMap<Row, (Option<Col>, Option<Col>)>,
+}
+
+impl ElasticMap {
+    /// Gets the elastic columns of the given row: the first one
+    /// measures how much the row is below its lower bound, the second
+    /// one how much it is above its upper bound. A column is absent
+    /// if the corresponding bound is infinite, or if the row is not
+    /// elastic.
+    pub fn slack_cols(&self, row: Row) -> (Option<Col>, Option<Col>) {
+        self.slacks.get(&row).copied().unwrap_or((None, None))
+    }
+    /// Gets by how much the given row of the original model is
+    /// violated in the solution of the elastic model.
+    pub fn violation(&self, solution: &Solution, row: Row) -> f64 {
+        let (below, above) = self.slack_cols(row);
+        below
+            .into_iter()
+            .chain(above)
+            .map(|c| solution.col(c))
+            .sum()
+    }
+    /// Gets the rows of the original model that are violated in the
+    /// solution of the elastic model, with their violation amount.
+    pub fn violations(&self, solution: &Solution) -> Vec<(Row, f64)> {
+        self.slacks
+            .keys()
+            .map(|&row| (row, self.violation(solution, row)))
+            .filter(|&(_, v)| v > ELASTIC_TOLERANCE)
+            .collect()
+    }
+}
+
+impl Model {
+    /// Gets a copy of the model where the given rows are elastic: a
+    /// nonnegative column is added to each finite bound of the rows,
+    /// allowing to violate it at the cost of `penalty` per unit in the
+    /// objective.
+    ///
+    /// Solving the elastic model gives the "closest" feasible
+    /// solution when the rows conflict; the returned `ElasticMap`
+    /// gives the violation of each row. If the objective sense is
+    /// `Sense::Ignore`, the elastic model minimizes the violations.
+    pub fn elastic(&self, rows: &[Row], penalty: f64) -> (Model, ElasticMap) {
+        let mut elastic = self.clone();
+        let penalty = match self.sense {
+            Sense::Maximize => -penalty,
+            Sense::Minimize => penalty,
+            Sense::Ignore => {
+                elastic.set_obj_sense(Sense::Minimize);
+                penalty
+            }
+        };
+        let mut map = ElasticMap::default();
+        for &row in rows {
+            let mut add_slack = |bound: f64, weight: f64| {
+                if bound.is_finite() {
+                    let slack = elastic.add_col();
+                    elastic.set_weight(row, slack, weight);
+                    elastic.set_obj_coeff(slack, penalty);
+                    Some(slack)
+                } else {
+                    None
+                }
+            };
+            let below = add_slack(self.row_lower[row.as_usize()], 1.);
+            let above = add_slack(self.row_upper[row.as_usize()], -1.);
+            map.slacks.insert(row, (below, above));
+        }
+        (elastic, map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn closest_feasible() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_col();
+        let y = m.add_col();
+        // Minimize x + 2y
+        m.set_obj_coeff(x, 1.);
+        m.set_obj_coeff(y, 2.);
+        m.set_obj_sense(Sense::Minimize);
+        // demand: x + y >= 10
+        let demand = m.add_row();
+        m.set_weight(demand, x, 1.);
+        m.set_weight(demand, y, 1.);
+        m.set_row_lower(demand, 10.);
+        // capacity: x + y <= 8
+        let capacity = m.add_row();
+        m.set_weight(capacity, x, 1.);
+        m.set_weight(capacity, y, 1.);
+        m.set_row_upper(capacity, 8.);
+
+        let (elastic, map) = m.elastic(&[demand], 100.);
+        assert_eq!(m.num_cols() + 1, elastic.num_cols());
+        assert!(map.slack_cols(demand).0.is_some());
+        assert_eq!(None, map.slack_cols(demand).1);
+        assert_eq!((None, None), map.slack_cols(capacity));
+
+        let sol = elastic.solve();
+        assert!(sol.raw().is_proven_optimal());
+        assert_eq!(8., sol.col(x));
+        assert_eq!(0., sol.col(y));
+        assert_eq!(vec![(demand, 2.)], map.violations(&sol));
+        assert_eq!(0., map.violation(&sol, capacity));
+    }
+}
@@ -0,0 +1,338 @@
+//! Rust-side feasibility checking of solutions.
+
+use crate::{Col, Model, Row};
+use std::os::raw::c_int;
+
+/// A constraint of a `Model` that is violated by a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// The activity of the row is below its lower bound.
+    RowLower {
+        /// The violated row.
+        row: Row,
+        /// The value of the linear expression of the row.
+        activity: f64,
+        /// How much the activity is below the lower bound.
+        violation: f64,
+    },
+    /// The activity of the row is above its upper bound.
+    RowUpper {
+        /// The violated row.
+        row: Row,
+        /// The value of the linear expression of the row.
+        activity: f64,
+        /// How much the activity is above the upper bound.
+        violation: f64,
+    },
+    /// The value of the column is below its lower bound.
+    ColLower {
+        /// The violated column.
+        col: Col,
+        /// The value of the column.
+        value: f64,
+        /// How much the value is below the lower bound.
+        violation: f64,
+    },
+    /// The value of the column is above its upper bound.
+    ColUpper {
+        /// The violated column.
+        col: Col,
+        /// The value of the column.
+        value: f64,
+        /// How much the value is above the upper bound.
+        violation: f64,
+    },
+    /// The value of an integer column is fractional.
+    Integrality {
+        /// The violated column.
+        col: Col,
+        /// The value of the column.
+        value: f64,
+        /// The distance of the value to the nearest integer.
+        violation: f64,
+    },
+    /// More than one column of a SOS1 constraint is non-zero.
+    Sos1 {
+        /// The index of the constraint, in the order of `Model::add_sos1` calls.
+        index: usize,
+        /// The non-zero columns of the constraint.
+        non_zero: Vec<Col>,
+        /// The sum of the absolute values of the non-zero columns,
+        /// except the largest one.
+        violation: f64,
+    },
+    /// The non-zero columns of a SOS2 constraint are not at most two
+    /// adjacent columns.
+    Sos2 {
+        /// The index of the constraint, in the order of `Model::add_sos2` calls.
+        index: usize,
+        /// The non-zero columns of the constraint.
+        non_zero: Vec<Col>,
+        /// The sum of the absolute values of the non-zero columns,
+        /// except the two adjacent ones with the largest sum.
+        violation: f64,
+    },
+}
+
+impl Violation {
+    /// Gets the amount by which the constraint is violated.
+    pub fn violation(&self) -> f64 {
+        match *self {
+            Violation::RowLower { violation, .. }
+            | Violation::RowUpper { violation, .. }
+            | Violation::ColLower { violation, .. }
+            | Violation::ColUpper { violation, .. }
+            | Violation::Integrality { violation, .. }
+            | Violation::Sos1 { violation, .. }
+            | Violation::Sos2 { violation, .. } => violation,
+        }
+    }
+}
+
+/// The result of `Model::check`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeasibilityReport {
+    violations: Vec<Violation>,
+}
+
+impl FeasibilityReport {
+    /// Returns whether no constraint is violated.
+    pub fn is_feasible(&self) -> bool {
+        self.violations.is_empty()
+    }
+    /// Gets the violated constraints.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+    /// Gets the largest violation amount, or 0 if the solution is feasible.
+    pub fn max_violation(&self) -> f64 {
+        self.violations
+            .iter()
+            .map(Violation::violation)
+            .fold(0., f64::max)
+    }
+}
+
+impl Model {
+    /// Checks the given column values against the row bounds, the
+    /// column bounds, the integrality of the integer columns and the
+    /// SOS constraints of the model.
+    ///
+    /// A constraint is violated if it is violated by more than `tol`.
+    /// `values` must contain one value per column.
+    pub fn check(&self, values: &[f64], tol: f64) -> FeasibilityReport {
+        assert_eq!(self.num_cols as usize, values.len());
+        let mut report = FeasibilityReport::default();
+        for (col, &value) in self.cols().zip(values) {
+            self.check_col(col, value, tol, &mut report.violations);
+        }
+        for (row, activity) in self.rows().zip(self.row_activities(values)) {
+            let lower = self.row_lower[row.as_usize()];
+            let upper = self.row_upper[row.as_usize()];
+            if activity < lower - tol {
+                report.violations.push(Violation::RowLower {
+                    row,
+                    activity,
+                    violation: lower - activity,
+                });
+            } else if activity > upper + tol {
+                report.violations.push(Violation::RowUpper {
+                    row,
+                    activity,
+                    violation: activity - upper,
+                });
+            }
+        }
+        for (index, set) in self.sos1.iter().enumerate() {
+            let non_zero = non_zero_members(set, values, tol);
+            if non_zero.len() > 1 {
+                let total: f64 = non_zero.iter().map(|m| m.value).sum();
+                let kept = non_zero.iter().map(|m| m.value).fold(0., f64::max);
+                report.violations.push(Violation::Sos1 {
+                    index,
+                    non_zero: non_zero.iter().map(|m| m.col).collect(),
+                    violation: total - kept,
+                });
+            }
+        }
+        for (index, set) in self.sos2.iter().enumerate() {
+            let non_zero = non_zero_members(set, values, tol);
+            let adjacent = match non_zero.as_slice() {
+                [] | [_] => true,
+                [a, b] => b.position - a.position == 1,
+                _ => false,
+            };
+            if !adjacent {
+                let total: f64 = non_zero.iter().map(|m| m.value).sum();
+                let kept = non_zero
+                    .iter()
+                    .map(|m| {
+                        let next = non_zero
+                            .iter()
+                            .find(|n| n.position == m.position + 1)
+                            .map_or(0., |n| n.value);
+                        m.value + next
+                    })
+                    .fold(0., f64::max);
+                report.violations.push(Violation::Sos2 {
+                    index,
+                    non_zero: non_zero.iter().map(|m| m.col).collect(),
+                    violation: total - kept,
+                });
+            }
+        }
+        report
+    }
+    /// Checks the bounds and integrality of a column value.
+    pub(crate) fn check_col(
+        &self,
+        col: Col,
+        value: f64,
+        tol: f64,
+        violations: &mut Vec<Violation>,
+    ) {
+        let lower = self.col_lower[col.as_usize()];
+        let upper = self.col_upper[col.as_usize()];
+        if value < lower - tol {
+            violations.push(Violation::ColLower {
+                col,
+                value,
+                violation: lower - value,
+            });
+        } else if value > upper + tol {
+            violations.push(Violation::ColUpper {
+                col,
+                value,
+                violation: value - upper,
+            });
+        }
+        let fractionality = (value - value.round()).abs();
+        if self.is_integer[col.as_usize()] && fractionality > tol {
+            violations.push(Violation::Integrality {
+                col,
+                value,
+                violation: fractionality,
+            });
+        }
+    }
+    /// Computes the value of the linear expression of every row.
+    pub(crate) fn row_activities(&self, values: &[f64]) -> Vec<f64> {
+        let mut activities = vec![0.; self.num_rows as usize];
+        for (col_weights, v) in self.weights.iter().zip(values) {
+            for (row, w) in col_weights {
+                activities[row.as_usize()] += w * v;
+            }
+        }
+        activities
+    }
+}
+
+/// A non-zero column of a SOS constraint.
+struct SosMember {
+    /// The position of the column in the constraint, sorted by weight.
+    position: usize,
+    col: Col,
+    /// The absolute value of the column.
+    value: f64,
+}
+
+/// Gets the non-zero columns of a SOS constraint, sorted by weight.
+fn non_zero_members(
+    (cols, weights): (&[c_int], &[f64]),
+    values: &[f64],
+    tol: f64,
+) -> Vec<SosMember> {
+    let mut order: Vec<usize> = (0..cols.len()).collect();
+    order.sort_by(|&a, &b| weights[a].total_cmp(&weights[b]));
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(position, i)| SosMember {
+            position,
+            col: Col(cols[i] as u32),
+            value: values[cols[i] as usize].abs(),
+        })
+        .filter(|m| m.value > tol)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_rows_bounds_and_integrality() {
+        let mut m = Model::default();
+        let x = m.add_integer();
+        let y = m.add_col();
+        m.set_col_upper(y, 3.);
+        // c: x + 2y <= 4
+        let c = m.add_row();
+        m.set_weight(c, x, 1.);
+        m.set_weight(c, y, 2.);
+        m.set_row_upper(c, 4.);
+
+        assert!(m.check(&[2., 1.], 1e-6).is_feasible());
+
+        let report = m.check(&[1.5, 3.5], 1e-6);
+        assert!(!report.is_feasible());
+        assert_eq!(
+            &[
+                Violation::Integrality {
+                    col: x,
+                    value: 1.5,
+                    violation: 0.5
+                },
+                Violation::ColUpper {
+                    col: y,
+                    value: 3.5,
+                    violation: 0.5
+                },
+                Violation::RowUpper {
+                    row: c,
+                    activity: 8.5,
+                    violation: 4.5
+                },
+            ],
+            report.violations()
+        );
+        assert_eq!(4.5, report.max_violation());
+    }
+
+    #[test]
+    fn check_sos() {
+        let mut m = Model::default();
+        let cols = [m.add_col(), m.add_col(), m.add_col()];
+        m.add_sos1(vec![(cols[0], 1.), (cols[1], 2.)]);
+        // The weights order the columns as cols[2], cols[0], cols[1]
+        m.add_sos2(vec![(cols[0], 2.), (cols[1], 3.), (cols[2], 1.)]);
+
+        assert!(m.check(&[1., 0., 2.], 1e-6).is_feasible());
+
+        let report = m.check(&[0., 1., 2.], 1e-6);
+        assert_eq!(
+            &[Violation::Sos2 {
+                index: 0,
+                non_zero: vec![cols[2], cols[1]],
+                violation: 1.
+            }],
+            report.violations()
+        );
+
+        let report = m.check(&[1., 3., 0.], 1e-6);
+        assert_eq!(
+            &[Violation::Sos1 {
+                index: 0,
+                non_zero: vec![cols[0], cols[1]],
+                violation: 1.
+            }],
+            report.violations()
+        );
+
+        // A NaN weight sorts after the others instead of panicking
+        let mut m = Model::default();
+        let cols = [m.add_col(), m.add_col()];
+        m.add_sos1(vec![(cols[0], f64::NAN), (cols[1], 1.)]);
+        assert!(!m.check(&[1., 1.], 1e-6).is_feasible());
+    }
+}
@@ -0,0 +1,152 @@
+//! User heuristics injecting solutions between batches of nodes.
+
+use crate::{Model, Sense, Solution, FEASIBILITY_TOLERANCE};
+
+impl Model {
+    /// Solves the model by batches of branch-and-bound nodes, letting
+    /// the heuristic propose complete solutions after each batch.
+    ///
+    /// The libcbc C API has no heuristic callback: each batch is a new
+    /// solve limited to `batch_nodes` nodes, which restarts the search
+    /// from scratch. After each batch that does not end the search, the
+    /// heuristic gets the solution of the batch, and returns candidate
+    /// solutions with one value per column. The candidates that are
+    /// not feasible (see `Model::check`) are discarded, and the best
+    /// solution among the candidates and the one found by CBC is the
+    /// initial solution of the next batch, the only thing carried over.
+    /// After `max_batches` batches, the last solve has no node limit.
+    ///
+    /// As the search tree is lost at each restart, the batches should
+    /// be large enough for CBC to make progress.
+    pub fn solve_with_heuristic<F>(
+        &self,
+        batch_nodes: u32,
+        max_batches: u32,
+        mut heuristic: F,
+    ) -> Solution
+    where
+        F: FnMut(&Solution) -> Vec<Vec<f64>>,
+    {
+        let mut work = self.clone();
+        work.set_parameter("maxNodes", &batch_nodes.to_string());
+        for _ in 0..max_batches {
+            let solution = work.solve();
+            if !solution.raw().is_node_limit_reached() {
+                return solution;
+            }
+            let found = self.cols().map(|col| solution.col(col)).collect();
+            let candidates = heuristic(&solution).into_iter().chain(Some(found));
+            if let Some(best) = self.best_feasible(candidates) {
+                work.remove_initial_solution();
+                work.add_initial_solution(self.cols().zip(best));
+            }
+        }
+        work.parameters = self.parameters.clone();
+        work.solve()
+    }
+    /// Gets the feasible candidate with the best objective value.
+    fn best_feasible<I: IntoIterator<Item = Vec<f64>>>(&self, candidates: I) -> Option<Vec<f64>> {
+        let mut best: Option<(f64, Vec<f64>)> = None;
+        for candidate in candidates {
+            if candidate.len() != self.num_cols as usize
+                || !self.check(&candidate, FEASIBILITY_TOLERANCE).is_feasible()
+            {
+                continue;
+            }
+            let obj = self.obj_value(&candidate);
+            let is_better = match (&best, self.sense) {
+                (None, _) => true,
+                (Some((best_obj, _)), Sense::Minimize) => obj < *best_obj,
+                (Some((best_obj, _)), Sense::Maximize) => obj > *best_obj,
+                (Some(_), Sense::Ignore) => false,
+            };
+            if is_better {
+                best = Some((obj, candidate));
+            }
+        }
+        best.map(|(_, candidate)| candidate)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::knapsack_model;
+    use crate::{Col, InitialSolutionStatus};
+
+    #[test]
+    fn best_feasible_candidate() {
+        let (m, _) = knapsack_model();
+        let candidates = vec![
+            vec![1., 1., 0., 0., 0.],
+            // Over capacity
+            vec![1., 1., 1., 1., 1.],
+            vec![1., 0., 0., 1., 0.],
+            // Fractional
+            vec![1., 0., 0., 1., 0.5],
+            // Wrong length
+            vec![1.],
+        ];
+        assert_eq!(Some(vec![1., 0., 0., 1., 0.]), m.best_feasible(candidates));
+        assert_eq!(None, m.best_feasible(vec![vec![0.5; 5]]));
+    }
+
+    /// A knapsack problem needing many nodes without cuts nor
+    /// heuristics: maximize sum((10 + i) x_i) s.t. sum(2 x_i) <= 11.
+    /// Also returns its optimum, the 5 most valuable items.
+    fn many_nodes_knapsack() -> (Model, Vec<Col>, Vec<f64>) {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        for (name, value) in &[
+            ("cuts", "off"),
+            ("heuristicsOnOff", "off"),
+            ("preprocess", "off"),
+        ] {
+            m.set_parameter(name, value);
+        }
+        let cols: Vec<Col> = (0..11).map(|_| m.add_binary()).collect();
+        let row = m.add_row();
+        m.set_row_upper(row, 11.);
+        for (i, &col) in cols.iter().enumerate() {
+            m.set_obj_coeff(col, 10. + i as f64);
+            m.set_weight(row, col, 2.);
+        }
+        m.set_obj_sense(Sense::Maximize);
+        let best = (0..11).map(|i| if i >= 6 { 1. } else { 0. }).collect();
+        (m, cols, best)
+    }
+
+    #[test]
+    fn injected_solutions() {
+        let (m, cols, best) = many_nodes_knapsack();
+        let mut calls = 0;
+        let sol = m.solve_with_heuristic(1, 5, |_| {
+            calls += 1;
+            vec![best.clone()]
+        });
+        assert!(calls >= 1);
+        assert!(sol.raw().is_proven_optimal());
+        assert_eq!(90., sol.raw().obj_value());
+        let values: Vec<f64> = cols.iter().map(|&c| sol.col(c)).collect();
+        assert_eq!(best, values);
+    }
+
+    #[test]
+    fn injected_solution_is_incumbent() {
+        let (m, _, best) = many_nodes_knapsack();
+        let mut batch_objs = vec![];
+        let sol = m.solve_with_heuristic(1, 3, |solution| {
+            batch_objs.push(solution.raw().obj_value());
+            vec![best.clone()]
+        });
+        assert!(!batch_objs.is_empty());
+        // The batches after the first one start from the injected
+        // optimum, which CBC keeps as its incumbent
+        assert!(batch_objs[1..].iter().all(|&obj| obj == 90.));
+        assert_eq!(
+            InitialSolutionStatus::Feasible,
+            sol.initial_solution_status()
+        );
+        assert_eq!(90., sol.raw().obj_value());
+    }
+}
@@ -0,0 +1,258 @@
+//! Irreducible infeasible subsystems.
+
+use crate::raw::SecondaryStatus;
+use crate::{Col, Model, Row, Sense, Solution};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// A constraint of an irreducible infeasible subsystem.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IisMember {
+    /// The bounds of the row.
+    Row(Row),
+    /// The lower bound of the column.
+    ColLower(Col),
+    /// The upper bound of the column.
+    ColUpper(Col),
+}
+
+/// An irreducible infeasible subsystem (IIS) of a model: a set of row
+/// and column bounds that are infeasible together, but that become
+/// feasible as soon as any one of them is removed.
+///
+/// The integrality and SOS constraints of the model are always kept,
+/// and thus not reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Iis {
+    members: Vec<(IisMember, String)>,
+}
+
+impl Iis {
+    /// Gets the constraints of the subsystem.
+    pub fn members(&self) -> impl Iterator<Item = IisMember> + '_ {
+        self.members.iter().map(|&(m, _)| m)
+    }
+    /// Gets the rows of the subsystem.
+    pub fn rows(&self) -> impl Iterator<Item = Row> + '_ {
+        self.members().filter_map(|m| match m {
+            IisMember::Row(row) => Some(row),
+            _ => None,
+        })
+    }
+    /// Gets the constraints of the subsystem with their names.
+    ///
+    /// The name of a constraint is the name of its row or column if
+    /// it was set, or a generated one (`R3`, `C12`) otherwise.
+    pub fn named_members(&self) -> impl Iterator<Item = (IisMember, &str)> {
+        self.members.iter().map(|(m, name)| (*m, name.as_str()))
+    }
+}
+
+impl fmt::Display for Iis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (member, name) in &self.members {
+            match member {
+                IisMember::Row(_) => writeln!(f, "row {}", name)?,
+                IisMember::ColLower(_) => writeln!(f, "lower bound of {}", name)?,
+                IisMember::ColUpper(_) => writeln!(f, "upper bound of {}", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Model {
+    /// Computes an irreducible infeasible subsystem of the model.
+    /// Returns `None` if the model is not proven infeasible.
+    ///
+    /// The subsystem is found with a deletion filter: every row and
+    /// column bound is removed in turn, and put back if the model
+    /// becomes feasible without it. This needs one solve per
+    /// constraint. If `elastic_phase` is true, the candidate rows are
+    /// first narrowed down by repeatedly solving the model with
+    /// elastic rows, which is usually faster on models with many rows.
+    pub fn find_iis(&self, elastic_phase: bool) -> Option<Iis> {
+        let mut work = self.clone();
+        work.initial_solutions.clear();
+        work.sense = Sense::Ignore;
+        for c in &mut work.obj_coefficients {
+            *c = 0.;
+        }
+        if !is_infeasible(&work.solve()) {
+            return None;
+        }
+
+        let mut rows: Vec<Row> = self
+            .rows()
+            .filter(|r| {
+                self.row_lower[r.as_usize()].is_finite() || self.row_upper[r.as_usize()].is_finite()
+            })
+            .collect();
+        if elastic_phase {
+            let kept = elastic_filter(&work, &rows);
+            for &row in &rows {
+                if !kept.contains(&row) {
+                    work.set_row_lower(row, f64::NEG_INFINITY);
+                    work.set_row_upper(row, f64::INFINITY);
+                }
+            }
+            rows.retain(|row| kept.contains(row));
+        }
+        let candidates = rows.into_iter().map(IisMember::Row).chain(
+            self.cols()
+                .filter(|c| self.col_lower[c.as_usize()].is_finite())
+                .map(IisMember::ColLower),
+        );
+        let candidates = candidates.chain(
+            self.cols()
+                .filter(|c| self.col_upper[c.as_usize()].is_finite())
+                .map(IisMember::ColUpper),
+        );
+
+        let mut members = vec![];
+        for candidate in candidates.collect::<Vec<_>>() {
+            let (lower, upper) = work.relax(candidate);
+            if !is_infeasible(&work.solve()) {
+                work.restore(candidate, lower, upper);
+                members.push((candidate, self.iis_member_name(candidate)));
+            }
+        }
+        Some(Iis { members })
+    }
+    /// Removes the bounds of the constraint. Returns the previous
+    /// lower and upper bounds.
+    fn relax(&mut self, member: IisMember) -> (f64, f64) {
+        match member {
+            IisMember::Row(row) => {
+                let i = row.as_usize();
+                let bounds = (self.row_lower[i], self.row_upper[i]);
+                self.row_lower[i] = f64::NEG_INFINITY;
+                self.row_upper[i] = f64::INFINITY;
+                bounds
+            }
+            IisMember::ColLower(col) => {
+                let i = col.as_usize();
+                let bounds = (self.col_lower[i], self.col_upper[i]);
+                self.col_lower[i] = f64::NEG_INFINITY;
+                bounds
+            }
+            IisMember::ColUpper(col) => {
+                let i = col.as_usize();
+                let bounds = (self.col_lower[i], self.col_upper[i]);
+                self.col_upper[i] = f64::INFINITY;
+                bounds
+            }
+        }
+    }
+    fn restore(&mut self, member: IisMember, lower: f64, upper: f64) {
+        match member {
+            IisMember::Row(row) => {
+                self.set_row_lower(row, lower);
+                self.set_row_upper(row, upper);
+            }
+            IisMember::ColLower(col) => self.set_col_lower(col, lower),
+            IisMember::ColUpper(col) => self.set_col_upper(col, upper),
+        }
+    }
+    fn iis_member_name(&self, member: IisMember) -> String {
+        match member {
+            IisMember::Row(row) => self
+                .row_name(row)
+                .map_or_else(|| format!("R{}", row.0), String::from),
+            IisMember::ColLower(col) | IisMember::ColUpper(col) => self
+                .col_name(col)
+                .map_or_else(|| format!("C{}", col.0), String::from),
+        }
+    }
+}
+
+/// Returns whether the solve proved the model infeasible.
+fn is_infeasible(sol: &Solution) -> bool {
+    let raw = sol.raw();
+    raw.is_proven_infeasible()
+        || raw.is_initial_solve_proven_primal_infeasible()
+        || raw.secondary_status() == SecondaryStatus::LinearRelaxationInfeasible
+}
+
+/// Elastic filter: solves the model with elastic rows, makes the rows
+/// that had to be violated hard, and repeats until the model is
+/// infeasible. Returns the hard rows, which are infeasible together
+/// with the column bounds.
+fn elastic_filter(work: &Model, rows: &[Row]) -> BTreeSet<Row> {
+    let mut hard = BTreeSet::new();
+    loop {
+        let soft: Vec<Row> = rows.iter().copied().filter(|r| !hard.contains(r)).collect();
+        let (elastic, map) = work.elastic(&soft, 1.);
+        let sol = elastic.solve();
+        if is_infeasible(&sol) {
+            return hard;
+        }
+        let violated = map.violations(&sol);
+        if violated.is_empty() || !sol.raw().is_proven_optimal() {
+            // Should not happen on an infeasible model: fall back to
+            // the deletion filter on every row.
+            return rows.iter().copied().collect();
+        }
+        hard.extend(violated.into_iter().map(|(row, _)| row));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn infeasible_model() -> (Model, [Row; 4]) {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_col();
+        let y = m.add_col();
+        m.set_col_name(x, "x");
+        // demand: x + y >= 10
+        let demand = m.add_row();
+        m.set_row_name(demand, "demand");
+        m.set_weight(demand, x, 1.);
+        m.set_weight(demand, y, 1.);
+        m.set_row_lower(demand, 10.);
+        // x <= 3
+        let cap_x = m.add_row();
+        m.set_weight(cap_x, x, 1.);
+        m.set_row_upper(cap_x, 3.);
+        // y <= 4
+        let cap_y = m.add_row();
+        m.set_weight(cap_y, y, 1.);
+        m.set_row_upper(cap_y, 4.);
+        // x - y <= 100, not part of the IIS
+        let other = m.add_row();
+        m.set_weight(other, x, 1.);
+        m.set_weight(other, y, -1.);
+        m.set_row_upper(other, 100.);
+        (m, [demand, cap_x, cap_y, other])
+    }
+
+    #[test]
+    fn deletion_filter() {
+        let (m, [demand, cap_x, cap_y, _]) = infeasible_model();
+        let iis = m.find_iis(false).unwrap();
+        assert_eq!(vec![demand, cap_x, cap_y], iis.rows().collect::<Vec<_>>());
+        assert_eq!(3, iis.members().count());
+        assert_eq!(
+            vec!["demand", "R1", "R2"],
+            iis.named_members().map(|(_, n)| n).collect::<Vec<_>>()
+        );
+        assert_eq!("row demand\nrow R1\nrow R2\n", iis.to_string());
+    }
+
+    #[test]
+    fn elastic_phase() {
+        let (m, [demand, cap_x, cap_y, _]) = infeasible_model();
+        let iis = m.find_iis(true).unwrap();
+        assert_eq!(vec![demand, cap_x, cap_y], iis.rows().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn feasible_model() {
+        let (mut m, [demand, ..]) = infeasible_model();
+        m.set_row_lower(demand, 5.);
+        assert_eq!(None, m.find_iis(false));
+    }
+}
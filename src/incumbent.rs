@@ -0,0 +1,141 @@
+//! Notification of the incumbent solutions found while solving.
+
+use crate::Model;
+use std::collections::HashSet;
+use std::ffi::CString;
+use std::sync::{Arc, Mutex};
+
+/// A callback registered with `Model::set_incumbent_callback`.
+pub(crate) type SharedIncumbentCallback = Arc<Mutex<dyn FnMut(f64, Vec<f64>) + Send>>;
+
+impl Model {
+    /// Sets a callback called with the objective value and the column
+    /// values of every new incumbent, i.e. every improving solution
+    /// found while solving.
+    ///
+    /// The objective value is computed from the objective
+    /// coefficients of the model. To process the solutions elsewhere,
+    /// the callback can send them to a `std::sync::mpsc::Sender`.
+    ///
+    /// The callback is called while solving: it must not solve a model
+    /// itself. If it panics, it is not called anymore, and the panic is
+    /// resumed once CBC returns. The clones of the model share the
+    /// callback.
+    ///
+    /// As CBC identifies the columns of the solutions by name, the
+    /// columns sharing a name (see `Model::set_col_name`) are renamed
+    /// in the `raw::Model`, see `Model::to_raw`.
+    ///
+    /// With the `dynamic-loading` feature, the callback is skipped if
+    /// the loaded libcbc lacks the C interface of the CBC master branch,
+    /// see `Solution::skipped`.
+    pub fn set_incumbent_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(f64, Vec<f64>) + Send + 'static,
+    {
+        self.incumbent_callback = Some(Arc::new(Mutex::new(callback)));
+    }
+    /// Removes the incumbent callback.
+    pub fn remove_incumbent_callback(&mut self) {
+        self.incumbent_callback = None;
+    }
+    /// Sets the incumbent callback on the `raw::Model`, unless the
+    /// loaded libcbc cannot call it.
+    pub(crate) fn add_incumbent_callback_to_raw(
+        &self,
+        raw: &mut crate::raw::Model,
+    ) -> Result<(), String> {
+        let callback = match &self.incumbent_callback {
+            Some(callback) => callback.clone(),
+            None => return Ok(()),
+        };
+        if !crate::has_cbc_function("Cbc_addIncumbentCallback") {
+            return Err(
+                "the incumbent callback needs the C interface of the CBC master branch".to_owned(),
+            );
+        }
+        make_col_names_unique(raw);
+        let obj_coefficients = self.obj_coefficients.clone();
+        raw.set_incumbent_callback(move |_, values| {
+            let obj = obj_coefficients
+                .iter()
+                .zip(&values)
+                .map(|(c, v)| c * v)
+                .sum();
+            let mut callback = callback.lock().unwrap_or_else(|e| e.into_inner());
+            callback(obj, values);
+        });
+        Ok(())
+    }
+}
+
+/// Renames the columns whose name is already used by another column,
+/// appending their index to it.
+fn make_col_names_unique(raw: &mut crate::raw::Model) {
+    let num_cols = raw.num_cols();
+    let names: Vec<Vec<u8>> = (0..num_cols)
+        .map(|i| raw.col_name(i).into_bytes())
+        .collect();
+    let mut used: HashSet<Vec<u8>> = names.iter().cloned().collect();
+    let mut seen = HashSet::new();
+    for (i, name) in names.iter().enumerate() {
+        if seen.insert(name) {
+            continue;
+        }
+        let mut suffix = i;
+        let unique = loop {
+            let mut candidate = name.clone();
+            candidate.extend(format!("_{}", suffix).bytes());
+            if used.insert(candidate.clone()) {
+                break candidate;
+            }
+            suffix += num_cols;
+        };
+        raw.set_col_name(i, &CString::new(unique).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::knapsack_model;
+
+    fn last_incumbent(mut m: Model) -> (f64, Vec<f64>) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        m.set_incumbent_callback(move |obj, values| {
+            let _ = sender.send((obj, values));
+        });
+        let sol = m.solve();
+        drop(m);
+        drop(sol);
+        let incumbents: Vec<(f64, Vec<f64>)> = receiver.iter().collect();
+        incumbents.last().cloned().expect("no incumbent")
+    }
+
+    #[test]
+    fn improving_solutions() {
+        let (m, _) = knapsack_model();
+        let (obj, values) = last_incumbent(m);
+        assert_eq!(16., obj);
+        assert_eq!(vec![1., 0., 0., 1., 1.], values);
+    }
+
+    #[test]
+    fn duplicate_names() {
+        let (mut m, cols) = knapsack_model();
+        for &col in &cols {
+            m.set_col_name(col, "x");
+        }
+        let (obj, values) = last_incumbent(m);
+        assert_eq!(16., obj);
+        assert_eq!(vec![1., 0., 0., 1., 1.], values);
+    }
+
+    #[test]
+    #[should_panic(expected = "no incumbent today")]
+    fn panicking_callback() {
+        let (mut m, _) = knapsack_model();
+        m.set_incumbent_callback(|_, _| panic!("no incumbent today"));
+        m.solve();
+    }
+}
@@ -0,0 +1,179 @@
+//! Indicator constraints, reformulated with big-M rows.
+
+use crate::{Col, ConstraintSense, Model, Row, Solution};
+
+/// An indicator constraint added by `Model::add_indicator`: when the
+/// binary column equals the active value, the linear constraint must
+/// hold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Indicator {
+    bin: Col,
+    active_value: bool,
+    rows: Vec<(Row, f64)>,
+}
+
+impl Indicator {
+    /// Gets the binary column controlling the constraint.
+    pub fn bin(&self) -> Col {
+        self.bin
+    }
+    /// Gets the value of the binary column for which the constraint
+    /// is enforced.
+    pub fn active_value(&self) -> bool {
+        self.active_value
+    }
+    /// Gets the rows emitted for the constraint, with their big-M
+    /// coefficient. An equality gives two rows.
+    pub fn rows(&self) -> &[(Row, f64)] {
+        &self.rows
+    }
+    /// Returns whether the constraint is enforced in the solution.
+    pub fn is_active(&self, solution: &Solution) -> bool {
+        (solution.col(self.bin) > 0.5) == self.active_value
+    }
+}
+
+impl Model {
+    /// Adds the indicator constraint `bin == active_value => expr sense rhs`.
+    ///
+    /// CBC has no native indicator constraints: they are reformulated
+    /// with rows whose big-M coefficient is derived from the bounds of
+    /// the columns of `expr`. Returns an error if `bin` is not a
+    /// binary column, or if the expression is unbounded in the
+    /// direction that the constraint restricts.
+    pub fn add_indicator<I: IntoIterator<Item = (Col, f64)>>(
+        &mut self,
+        bin: Col,
+        active_value: bool,
+        expr: I,
+        sense: ConstraintSense,
+        rhs: f64,
+    ) -> Result<&Indicator, String> {
+        if !self.is_binary(bin) {
+            return Err(format!("column {} of the indicator is not binary", bin.0));
+        }
+        let expr: Vec<(Col, f64)> = expr.into_iter().collect();
+        let (min, max) = self.expr_bounds(&expr);
+        let upper_m = match sense {
+            ConstraintSense::LessOrEqual | ConstraintSense::Equal => Some(max - rhs),
+            ConstraintSense::GreaterOrEqual => None,
+        };
+        let lower_m = match sense {
+            ConstraintSense::GreaterOrEqual | ConstraintSense::Equal => Some(rhs - min),
+            ConstraintSense::LessOrEqual => None,
+        };
+        if upper_m.into_iter().chain(lower_m).any(f64::is_infinite) {
+            return Err(format!(
+                "cannot derive a big-M for the indicator on column {}: the expression is unbounded",
+                bin.0
+            ));
+        }
+
+        let mut rows = vec![];
+        // If the constraint is always satisfied, M is 0 and the row
+        // does not depend on the binary column.
+        if let Some(m) = upper_m.map(|m| m.max(0.)) {
+            // expr <= rhs + M (1 - bin), or expr <= rhs + M bin
+            let row = if active_value {
+                let expr = expr.iter().copied().chain(Some((bin, m)));
+                self.add_constraint(expr, ConstraintSense::LessOrEqual, rhs + m)
+            } else {
+                let expr = expr.iter().copied().chain(Some((bin, -m)));
+                self.add_constraint(expr, ConstraintSense::LessOrEqual, rhs)
+            };
+            rows.push((row, m));
+        }
+        if let Some(m) = lower_m.map(|m| m.max(0.)) {
+            // expr >= rhs - M (1 - bin), or expr >= rhs - M bin
+            let row = if active_value {
+                let expr = expr.iter().copied().chain(Some((bin, -m)));
+                self.add_constraint(expr, ConstraintSense::GreaterOrEqual, rhs - m)
+            } else {
+                let expr = expr.iter().copied().chain(Some((bin, m)));
+                self.add_constraint(expr, ConstraintSense::GreaterOrEqual, rhs)
+            };
+            rows.push((row, m));
+        }
+        self.indicators.push(Indicator {
+            bin,
+            active_value,
+            rows,
+        });
+        Ok(self.indicators.last().unwrap())
+    }
+    /// Gets the indicator constraints added with `add_indicator`.
+    pub fn indicators(&self) -> &[Indicator] {
+        &self.indicators
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Sense;
+
+    #[test]
+    fn big_m_from_bounds() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let assigned = m.add_binary();
+        let start = m.add_col();
+        m.set_col_upper(start, 20.);
+        let unbounded = m.add_col();
+
+        // assigned => start >= 8
+        let indicator = m
+            .add_indicator(
+                assigned,
+                true,
+                vec![(start, 1.)],
+                ConstraintSense::GreaterOrEqual,
+                8.,
+            )
+            .unwrap();
+        assert_eq!(1, indicator.rows().len());
+        assert_eq!(8., indicator.rows()[0].1);
+        // !assigned => start == 0
+        m.add_indicator(
+            assigned,
+            false,
+            vec![(start, 1.)],
+            ConstraintSense::Equal,
+            0.,
+        )
+        .unwrap();
+        assert_eq!(2, m.indicators().len());
+        assert_eq!(
+            vec![20., 0.],
+            m.indicators()[1]
+                .rows()
+                .iter()
+                .map(|&(_, m)| m)
+                .collect::<Vec<_>>()
+        );
+
+        assert!(m
+            .add_indicator(
+                assigned,
+                true,
+                vec![(unbounded, 1.)],
+                ConstraintSense::LessOrEqual,
+                1.
+            )
+            .is_err());
+        assert!(m
+            .add_indicator(start, true, vec![], ConstraintSense::LessOrEqual, 1.)
+            .is_err());
+        assert_eq!(2, m.indicators().len());
+
+        // Maximize 10 assigned - start
+        m.set_obj_coeff(assigned, 10.);
+        m.set_obj_coeff(start, -1.);
+        m.set_obj_sense(Sense::Maximize);
+        let sol = m.solve();
+        assert_eq!(1., sol.col(assigned));
+        assert_eq!(8., sol.col(start));
+        assert!(m.indicators()[0].is_active(&sol));
+        assert!(!m.indicators()[1].is_active(&sol));
+    }
+}
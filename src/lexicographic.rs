@@ -0,0 +1,143 @@
+//! Lexicographic optimization of several objectives.
+
+use crate::{Col, ConstraintSense, Model, Row, Sense, Solution};
+use std::time::{Duration, Instant};
+
+/// A stage of `Model::solve_lexicographic`: the solve of one objective.
+pub struct LexicographicStage {
+    solution: Solution,
+    objective_value: f64,
+    bound_row: Option<Row>,
+    duration: Duration,
+}
+
+impl LexicographicStage {
+    /// Gets the solution of the stage.
+    pub fn solution(&self) -> &Solution {
+        &self.solution
+    }
+    /// Gets the value of the objective of the stage in its solution.
+    pub fn objective_value(&self) -> f64 {
+        self.objective_value
+    }
+    /// Gets the row added to keep the objective of the stage close to
+    /// its optimum in the next stages, if any.
+    pub fn bound_row(&self) -> Option<Row> {
+        self.bound_row
+    }
+    /// Gets the time spent solving the stage.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+    /// Gets the number of simplex iterations of the stage.
+    pub fn iteration_count(&self) -> i32 {
+        self.solution.raw().iteration_count()
+    }
+}
+
+impl Model {
+    /// Optimizes the given objectives in order of priority, each one
+    /// being a linear expression with a relative tolerance.
+    ///
+    /// The model is solved with the first objective, which replaces
+    /// the objective of the model. A row then bounds it to its optimum,
+    /// degraded by at most `tolerance` times its absolute value, and
+    /// the model is solved with the next objective, starting from the
+    /// previous solution, and so on.
+    ///
+    /// All the objectives are optimized in the sense of the model,
+    /// minimizing if it is `Sense::Ignore`: negate the coefficients of
+    /// the objectives to optimize in the other sense. If a stage is
+    /// not solved to optimality, the next ones are not solved.
+    pub fn solve_lexicographic(
+        &self,
+        objectives: &[(&[(Col, f64)], f64)],
+    ) -> Vec<LexicographicStage> {
+        let mut work = self.objective_work_model();
+        let mut stages: Vec<LexicographicStage> = vec![];
+        for (i, &(objective, tolerance)) in objectives.iter().enumerate() {
+            work.replace_objective(objective.iter().copied());
+            if let Some(previous) = stages.last() {
+                // The previous solution is feasible in this stage
+                work.remove_initial_solution();
+                work.set_initial_solution(&previous.solution);
+            }
+            let start = Instant::now();
+            let solution = work.solve();
+            let duration = start.elapsed();
+            let optimal = solution.raw().is_proven_optimal();
+            let objective_value = objective
+                .iter()
+                .map(|&(col, coeff)| coeff * solution.col(col))
+                .sum::<f64>();
+
+            let bound_row = if optimal && i + 1 < objectives.len() {
+                let slack = tolerance * objective_value.abs();
+                let expr = objective.iter().copied();
+                Some(match work.sense {
+                    Sense::Maximize => work.add_constraint(
+                        expr,
+                        ConstraintSense::GreaterOrEqual,
+                        objective_value - slack,
+                    ),
+                    _ => work.add_constraint(
+                        expr,
+                        ConstraintSense::LessOrEqual,
+                        objective_value + slack,
+                    ),
+                })
+            } else {
+                None
+            };
+            stages.push(LexicographicStage {
+                solution,
+                objective_value,
+                bound_row,
+                duration,
+            });
+            if !optimal {
+                break;
+            }
+        }
+        stages
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn service_then_cost(service_tolerance: f64) -> Vec<LexicographicStage> {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_integer();
+        m.set_col_upper(x, 10.);
+        let y = m.add_integer();
+        m.set_col_upper(y, 10.);
+        m.add_constraint(vec![(x, 1.), (y, 1.)], ConstraintSense::LessOrEqual, 12.);
+        m.set_obj_sense(Sense::Maximize);
+        // Maximize the service x + y, then minimize the cost 2x + 3y
+        m.solve_lexicographic(&[
+            (&[(x, 1.), (y, 1.)], service_tolerance),
+            (&[(x, -2.), (y, -3.)], 0.),
+        ])
+    }
+
+    #[test]
+    fn strict_priorities() {
+        let stages = service_then_cost(0.);
+        assert_eq!(2, stages.len());
+        assert_eq!(12., stages[0].objective_value());
+        assert!(stages[0].bound_row().is_some());
+        assert_eq!(-26., stages[1].objective_value());
+        assert_eq!(None, stages[1].bound_row());
+    }
+
+    #[test]
+    fn tolerance() {
+        // The service can be degraded down to 9
+        let stages = service_then_cost(0.25);
+        assert_eq!(12., stages[0].objective_value());
+        assert_eq!(-18., stages[1].objective_value());
+    }
+}
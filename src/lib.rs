@@ -23,10 +23,40 @@
 
 #![deny(missing_docs)]
 
+mod branching;
+#[cfg(cbc_master)]
+mod cut_generator;
+mod elastic;
+mod feasibility;
+mod heuristic;
+mod iis;
+#[cfg(cbc_master)]
+mod incumbent;
+mod indicator;
+mod lexicographic;
+mod logical;
+mod minmax;
+mod pareto;
+mod piecewise;
+mod pool;
 pub mod raw;
+mod semicontinuous;
+mod separation;
 mod sos_constraints;
 
-pub use raw::Sense;
+#[cfg(cbc_master)]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+pub use cut_generator::{CutGenerator, CutSink};
+pub use elastic::ElasticMap;
+pub use feasibility::{FeasibilityReport, Violation};
+pub use iis::{Iis, IisMember};
+pub use indicator::Indicator;
+pub use lexicographic::LexicographicStage;
+pub use minmax::MinMaxFormulation;
+pub use pareto::{ParetoMethod, ParetoPoint};
+pub use piecewise::PiecewiseFormulation;
+pub use raw::{BranchDirection, Sense};
+pub use separation::Cut;
 
 use crate::raw::SOSConstraintType;
 use crate::sos_constraints::SOSConstraints;
@@ -34,8 +64,16 @@ use std::collections::BTreeMap;
 use std::ffi::CString;
 use std::os::raw::c_int;
 
+/// Tolerance used when checking candidate solutions.
+const FEASIBILITY_TOLERANCE: f64 = 1e-6;
+/// Node limit of the search completing a partial initial solution.
+const COMPLETION_MAX_NODES: &str = "1000";
+/// Time limit, in seconds, of the search completing a partial initial
+/// solution.
+const COMPLETION_SECONDS: &str = "5";
+
 /// A column identifier.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Col(u32);
 
 impl Col {
@@ -45,7 +83,7 @@ impl Col {
 }
 
 /// A row identifier.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Row(u32);
 impl Row {
     fn as_usize(self) -> usize {
@@ -66,8 +104,18 @@ pub struct Model {
     weights: Vec<BTreeMap<Row, f64>>,
     is_integer: Vec<bool>,
     sense: Sense,
-    initial_solution: Option<Vec<f64>>,
+    initial_solutions: Vec<Vec<Option<f64>>>,
     parameters: BTreeMap<CString, CString>,
+    col_names: BTreeMap<Col, CString>,
+    row_names: BTreeMap<Row, CString>,
+    indicators: Vec<Indicator>,
+    semicontinuous: BTreeMap<Col, (f64, Col)>,
+    branch_priorities: BTreeMap<Col, i32>,
+    branch_directions: BTreeMap<Col, BranchDirection>,
+    #[cfg(cbc_master)]
+    cut_generators: Vec<cut_generator::RegisteredCutGenerator>,
+    #[cfg(cbc_master)]
+    incumbent_callback: Option<incumbent::SharedIncumbentCallback>,
     sos1: SOSConstraints,
     sos2: SOSConstraints,
 }
@@ -81,24 +129,46 @@ impl Model {
     pub fn num_cols(&self) -> u32 {
         self.num_cols
     }
-    /// Removes the initial solution.
+    /// Removes the initial solution, and all the other candidate
+    /// initial solutions.
     pub fn remove_initial_solution(&mut self) {
-        self.initial_solution = None;
+        self.initial_solutions.clear();
     }
     /// Sets the column value to the initial solution.
     ///
-    /// If the solution is not present, it will be initialized with 0
-    /// for all coefficients.
+    /// The initial solution may be partial: the columns that are not
+    /// set are left free, and are completed when solving (see
+    /// `InitialSolutionStatus`).
     pub fn set_col_initial_solution(&mut self, col: Col, value: f64) {
-        if self.initial_solution.is_none() {
-            self.initial_solution = Some(vec![0.; self.num_cols as usize]);
+        if self.initial_solutions.is_empty() {
+            self.initial_solutions
+                .push(vec![None; self.num_cols as usize]);
         }
-        let sol = self.initial_solution.as_mut().unwrap();
-        sol[col.as_usize()] = value;
+        self.initial_solutions[0][col.as_usize()] = Some(value);
     }
     /// Gets the column value to the initial solution.
+    ///
+    /// Returns `None` if the column is not set in the initial solution.
     pub fn get_col_initial_solution(&self, col: Col) -> Option<f64> {
-        self.initial_solution.as_ref().map(|s| s[col.as_usize()])
+        self.initial_solutions
+            .first()
+            .and_then(|s| s[col.as_usize()])
+    }
+    /// Adds a candidate initial solution, possibly partial.
+    ///
+    /// The libcbc C API only accepts one initial solution: when
+    /// solving, the candidates that are not feasible are discarded,
+    /// and the one with the best objective value is given to CBC.
+    pub fn add_initial_solution<I: IntoIterator<Item = (Col, f64)>>(&mut self, values: I) {
+        let mut sol = vec![None; self.num_cols as usize];
+        for (col, value) in values {
+            sol[col.as_usize()] = Some(value);
+        }
+        self.initial_solutions.push(sol);
+    }
+    /// Gets the number of candidate initial solutions.
+    pub fn num_initial_solutions(&self) -> usize {
+        self.initial_solutions.len()
     }
     /// Sets the initial solution from a `Solution`.
     pub fn set_initial_solution(&mut self, solution: &Solution) {
@@ -129,6 +199,30 @@ impl Model {
             self.set_parameter(k.as_ref(), v.as_ref());
         }
     }
+    /// Sets the name of the given column.
+    ///
+    /// Names containing a nul byte are ignored.
+    pub fn set_col_name(&mut self, col: Col, name: &str) {
+        if let Ok(name) = CString::new(name) {
+            self.col_names.insert(col, name);
+        }
+    }
+    /// Gets the name of the given column, if set.
+    pub fn col_name(&self, col: Col) -> Option<&str> {
+        self.col_names.get(&col).and_then(|n| n.to_str().ok())
+    }
+    /// Sets the name of the given row.
+    ///
+    /// Names containing a nul byte are ignored.
+    pub fn set_row_name(&mut self, row: Row, name: &str) {
+        if let Ok(name) = CString::new(name) {
+            self.row_names.insert(row, name);
+        }
+    }
+    /// Gets the name of the given row, if set.
+    pub fn row_name(&self, row: Row) -> Option<&str> {
+        self.row_names.get(&row).and_then(|n| n.to_str().ok())
+    }
     /// Gets an iterator on the row identifiers.
     pub fn rows(&self) -> impl Iterator<Item = Row> {
         (0..self.num_rows).map(Row)
@@ -148,8 +242,10 @@ impl Model {
         self.weights.push(Default::default());
         self.is_integer.push(false);
         self.col_lower.push(0.);
-        self.col_upper.push(std::f64::INFINITY);
-        self.initial_solution.as_mut().map(|sol| sol.push(0.));
+        self.col_upper.push(f64::INFINITY);
+        for sol in &mut self.initial_solutions {
+            sol.push(None);
+        }
         col
     }
     /// Adds an integer variable to the model.
@@ -175,8 +271,8 @@ impl Model {
     pub fn add_row(&mut self) -> Row {
         let row = Row(self.num_rows);
         self.num_rows += 1;
-        self.row_lower.push(std::f64::NEG_INFINITY);
-        self.row_upper.push(std::f64::INFINITY);
+        self.row_lower.push(f64::NEG_INFINITY);
+        self.row_upper.push(f64::INFINITY);
         row
     }
     /// Sets the weight corresponding to the given row and column in
@@ -217,6 +313,24 @@ impl Model {
     pub fn set_obj_coeff(&mut self, col: Col, value: f64) {
         self.obj_coefficients[col.as_usize()] = value;
     }
+    /// Clones the model to optimize other objectives in its sense,
+    /// minimizing if it is `Sense::Ignore`.
+    pub(crate) fn objective_work_model(&self) -> Model {
+        let mut work = self.clone();
+        if work.sense == Sense::Ignore {
+            work.sense = Sense::Minimize;
+        }
+        work
+    }
+    /// Replaces the objective by the given linear expression.
+    pub(crate) fn replace_objective<I: IntoIterator<Item = (Col, f64)>>(&mut self, expr: I) {
+        for c in &mut self.obj_coefficients {
+            *c = 0.;
+        }
+        for (col, coeff) in expr {
+            self.obj_coefficients[col.as_usize()] += coeff;
+        }
+    }
     /// Sets the upper bound of the given row.
     pub fn set_row_upper(&mut self, row: Row, value: f64) {
         self.row_upper[row.as_usize()] = value;
@@ -232,6 +346,43 @@ impl Model {
         self.set_row_upper(row, value);
         self.set_row_lower(row, value);
     }
+    /// Adds the constraint `expr sense rhs`, where `expr` is a linear
+    /// expression given as (column, coefficient) pairs. Returns the
+    /// corresponding row identifier.
+    pub fn add_constraint<I: IntoIterator<Item = (Col, f64)>>(
+        &mut self,
+        expr: I,
+        sense: ConstraintSense,
+        rhs: f64,
+    ) -> Row {
+        let row = self.add_row();
+        for (col, coeff) in expr {
+            let weight = self.weights[col.as_usize()].get(&row).unwrap_or(&0.) + coeff;
+            self.set_weight(row, col, weight);
+        }
+        match sense {
+            ConstraintSense::LessOrEqual => self.set_row_upper(row, rhs),
+            ConstraintSense::GreaterOrEqual => self.set_row_lower(row, rhs),
+            ConstraintSense::Equal => self.set_row_equal(row, rhs),
+        }
+        row
+    }
+    /// Computes the minimum and maximum values of a linear expression
+    /// given the column bounds.
+    pub(crate) fn expr_bounds(&self, expr: &[(Col, f64)]) -> (f64, f64) {
+        let mut min = 0.;
+        let mut max = 0.;
+        for &(col, coeff) in expr {
+            let lower = coeff * self.col_lower[col.as_usize()];
+            let upper = coeff * self.col_upper[col.as_usize()];
+            // 0 * inf is NaN, but the column does not contribute
+            if coeff != 0. {
+                min += lower.min(upper);
+                max += lower.max(upper);
+            }
+        }
+        (min, max)
+    }
 
     /// Add a special ordered set constraint, preventing all but one variable
     /// in a set from being non-zero at the same time.
@@ -257,7 +408,27 @@ impl Model {
         self.sense = sense;
     }
     /// Construct a `raw::Model` corresponding to the current state.
+    ///
+    /// The parts of the model that cannot be given to CBC are left
+    /// out, see `Solution::skipped`. So are the partial initial
+    /// solutions that libcbc cannot complete itself (libcbc < 2.10),
+    /// which only `solve` completes. With an incumbent callback, the
+    /// columns sharing a name are renamed, appending their index, as
+    /// CBC identifies the columns of the incumbents by name.
     pub fn to_raw(&self) -> raw::Model {
+        self.build_raw(false, false).0
+    }
+    /// Builds the `raw::Model`. If `relaxed` is true, the integer
+    /// columns are made continuous, and the SOS constraints, the
+    /// initial solutions and the branching priorities are ignored.
+    /// The partial initial solutions that libcbc cannot complete itself
+    /// are completed if `complete` is true, and ignored otherwise.
+    /// Also returns why parts of the model were skipped.
+    fn build_raw(
+        &self,
+        relaxed: bool,
+        complete: bool,
+    ) -> (raw::Model, InitialSolutionStatus, Vec<String>) {
         let mut start = Vec::with_capacity(self.num_cols as usize + 1);
         let mut index = Vec::with_capacity(self.num_cols.max(self.num_rows) as usize);
         let mut value = Vec::with_capacity(self.num_cols.max(self.num_rows) as usize);
@@ -283,32 +454,260 @@ impl Model {
             Some(&self.row_upper),
         );
         for (col, &is_int) in self.is_integer.iter().enumerate() {
-            if is_int {
+            if is_int && !relaxed {
                 raw.set_integer(col);
             } else {
                 raw.set_continuous(col);
             }
         }
+        for (col, name) in &self.col_names {
+            raw.set_col_name(col.as_usize(), name);
+        }
+        for (row, name) in &self.row_names {
+            raw.set_row_name(row.as_usize(), name);
+        }
         raw.set_obj_sense(self.sense);
         for (k, v) in &self.parameters {
             raw.set_parameter(k, v);
         }
-        if let Some(sol) = &self.initial_solution {
-            raw.set_initial_solution(sol);
+        if relaxed {
+            return (raw, InitialSolutionStatus::Absent, vec![]);
         }
+        let initial_solution_status = self.add_initial_solution_to_raw(&mut raw, complete);
         self.sos1.add_to_raw(&mut raw, SOSConstraintType::Type1);
         self.sos2.add_to_raw(&mut raw, SOSConstraintType::Type2);
-        raw
+        let mut skipped = vec![];
+        skipped.extend(self.add_branching_to_raw(&mut raw).err());
+        #[cfg(cbc_master)]
+        skipped.extend(self.add_cut_generators_to_raw(&mut raw).err());
+        #[cfg(cbc_master)]
+        skipped.extend(self.add_incumbent_callback_to_raw(&mut raw).err());
+        (raw, initial_solution_status, skipped)
+    }
+    fn add_initial_solution_to_raw(
+        &self,
+        raw: &mut raw::Model,
+        complete: bool,
+    ) -> InitialSolutionStatus {
+        if self.initial_solutions.is_empty() {
+            return InitialSolutionStatus::Absent;
+        }
+        let mut best: Option<(f64, Vec<f64>, InitialSolutionStatus)> = None;
+        #[cfg(cbc_ge_2_10)]
+        let has_mip_start = has_cbc_function("Cbc_setMIPStartI");
+        #[cfg(cbc_ge_2_10)]
+        let mut first_partial = None;
+        for sol in &self.initial_solutions {
+            let (full, status) = match sol.iter().copied().collect::<Option<Vec<f64>>>() {
+                Some(full) => (full, InitialSolutionStatus::Feasible),
+                #[cfg(cbc_ge_2_10)]
+                None if has_mip_start => {
+                    if first_partial.is_none() && self.is_partially_feasible(sol) {
+                        first_partial = Some(sol);
+                    }
+                    continue;
+                }
+                None if complete => match self.complete_initial_solution(sol) {
+                    Some(full) => (full, InitialSolutionStatus::Completed),
+                    None => continue,
+                },
+                None => continue,
+            };
+            if !self.check(&full, FEASIBILITY_TOLERANCE).is_feasible() {
+                continue;
+            }
+            let obj = self.obj_value(&full);
+            let is_better = match (&best, self.sense) {
+                (None, _) => true,
+                (Some((best_obj, _, _)), Sense::Minimize) => obj < *best_obj,
+                (Some((best_obj, _, _)), Sense::Maximize) => obj > *best_obj,
+                (Some(_), Sense::Ignore) => false,
+            };
+            if is_better {
+                best = Some((obj, full, status));
+            }
+        }
+        if let Some((_, full, status)) = best {
+            raw.set_initial_solution(&full);
+            return status;
+        }
+        #[cfg(cbc_ge_2_10)]
+        if let Some(sol) = first_partial {
+            let (indices, values): (Vec<c_int>, Vec<f64>) = sol
+                .iter()
+                .enumerate()
+                .filter_map(|(i, v)| v.map(|v| (i as c_int, v)))
+                .unzip();
+            raw.set_mip_start_i(&indices, &values);
+            return InitialSolutionStatus::Partial;
+        }
+        InitialSolutionStatus::Rejected
+    }
+    /// Completes a partial initial solution by solving the model
+    /// with the given columns fixed to their values, stopping at the
+    /// first feasible solution found, or at the limits
+    /// `COMPLETION_MAX_NODES` and `COMPLETION_SECONDS`.
+    ///
+    /// This mimics what `Cbc_setMIPStart` does on newer versions of
+    /// libcbc.
+    fn complete_initial_solution(&self, partial: &[Option<f64>]) -> Option<Vec<f64>> {
+        let mut fixed = self.clone();
+        fixed.initial_solutions.clear();
+        fixed.set_parameter("maxSolutions", "1");
+        fixed.set_parameter("maxNodes", COMPLETION_MAX_NODES);
+        fixed.set_parameter("seconds", COMPLETION_SECONDS);
+        for (col, value) in self.cols().zip(partial) {
+            if let Some(value) = *value {
+                fixed.set_col_lower(col, value);
+                fixed.set_col_upper(col, value);
+            }
+        }
+        let full = fixed.solve().col_solution.into_vec();
+        if self.check(&full, FEASIBILITY_TOLERANCE).is_feasible() {
+            Some(full)
+        } else {
+            None
+        }
+    }
+    /// Computes the objective value of the given column values.
+    fn obj_value(&self, values: &[f64]) -> f64 {
+        self.obj_coefficients
+            .iter()
+            .zip(values)
+            .map(|(c, v)| c * v)
+            .sum()
+    }
+    /// Checks the values of a partial solution that are set.
+    #[cfg(cbc_ge_2_10)]
+    fn is_partially_feasible(&self, partial: &[Option<f64>]) -> bool {
+        let mut violations = vec![];
+        for (col, value) in self.cols().zip(partial) {
+            if let Some(value) = *value {
+                self.check_col(col, value, FEASIBILITY_TOLERANCE, &mut violations);
+            }
+        }
+        violations.is_empty()
     }
     /// Solves the model. Returns the solution.
     pub fn solve(&self) -> Solution {
-        let mut raw = self.to_raw();
+        self.solve_raw(false)
+    }
+    /// Solves the linear relaxation of the model: the integer columns
+    /// are considered continuous, and the SOS constraints are
+    /// dropped. The model itself is not modified.
+    ///
+    /// The returned solution is flagged with `Solution::is_relaxation`.
+    pub fn solve_relaxation(&self) -> Solution {
+        self.solve_raw(true)
+    }
+    fn solve_raw(&self, relaxed: bool) -> Solution {
+        let (mut raw, initial_solution_status, skipped) = self.build_raw(relaxed, true);
         raw.solve();
-        let col_solution = raw.col_solution().into();
-        Solution { raw, col_solution }
+        let mut col_solution: Box<[f64]> = raw.col_solution().into();
+        if !relaxed {
+            self.snap_semicontinuous(&mut col_solution);
+        }
+        Solution {
+            raw,
+            col_solution,
+            initial_solution_status,
+            skipped,
+            is_relaxation: relaxed,
+        }
+    }
+    /// Gets a continuous copy of the model where every integer
+    /// column is fixed to its value in the given solution.
+    ///
+    /// The SOS constraints are removed, their columns that are null
+    /// in the solution, up to a tolerance of 1e-6, being fixed to 0
+    /// so that the constraints still hold. The initial solutions are not kept.
+    pub fn with_fixed_integers(&self, solution: &Solution) -> Model {
+        let mut lp = self.clone();
+        lp.initial_solutions.clear();
+        lp.sos1 = Default::default();
+        lp.sos2 = Default::default();
+        for col in self.cols() {
+            if self.is_integer[col.as_usize()] {
+                let value = solution.col(col).round();
+                lp.set_continuous(col);
+                lp.set_col_lower(col, value);
+                lp.set_col_upper(col, value);
+            }
+        }
+        for (cols, _) in self.sos1.iter().chain(self.sos2.iter()) {
+            for &c in cols {
+                let col = Col(c as u32);
+                if solution.col(col).abs() <= FEASIBILITY_TOLERANCE {
+                    lp.set_col_lower(col, 0.);
+                    lp.set_col_upper(col, 0.);
+                }
+            }
+        }
+        lp
+    }
+    /// Solves the linear problem given by `with_fixed_integers`.
+    ///
+    /// The returned solution gives the row activities, reduced costs
+    /// and row prices of the linear problem, i.e. the marginal costs
+    /// around the given MILP solution.
+    pub fn solve_fixed_integers(&self, solution: &Solution) -> Solution {
+        self.with_fixed_integers(solution).solve()
+    }
+    /// Solves the model, then fixes the integer columns to their
+    /// values in the solution and solves the resulting linear
+    /// problem. Returns the solution of the linear problem, whose
+    /// row prices and reduced costs are meaningful.
+    ///
+    /// If the model has no integer columns or no solution is found,
+    /// the solution of the model is returned as is.
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    pub fn solve_with_duals(&self) -> Solution {
+        let sol = self.solve();
+        let has_integers = self.is_integer.iter().any(|&is_int| is_int);
+        let has_sos = !self.sos1.is_empty() || !self.sos2.is_empty();
+        if !(has_integers || has_sos) || !sol.raw().is_proven_optimal() {
+            return sol;
+        }
+        self.solve_fixed_integers(&sol)
     }
 }
 
+/// Sense of a linear constraint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConstraintSense {
+    /// The expression must be less than or equal to the right-hand side.
+    LessOrEqual,
+    /// The expression must be greater than or equal to the right-hand side.
+    GreaterOrEqual,
+    /// The expression must be equal to the right-hand side.
+    Equal,
+}
+
+/// What happened to the initial solution of a `Model` when it was
+/// handed to CBC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InitialSolutionStatus {
+    /// The model has no initial solution.
+    Absent,
+    /// The initial solution is complete, was checked to be feasible
+    /// (see `Model::check`), and was given to CBC as is.
+    Feasible,
+    /// The partial initial solution was given to CBC, which completes
+    /// it itself (libcbc >= 2.10). The C interface of CBC does not
+    /// report whether the completion succeeded, and thus whether the
+    /// start was used.
+    Partial,
+    /// The partial initial solution was completed into a feasible
+    /// solution before being given to CBC.
+    Completed,
+    /// No initial solution was given to CBC: the complete candidates
+    /// are infeasible, and the partial ones could not be completed
+    /// into a feasible solution or, on libcbc >= 2.10, have infeasible
+    /// column values.
+    Rejected,
+}
+
 /// A solution to a MILP problem.
 ///
 /// This is a thin wrapper over a `raw::Model` with accessors using
@@ -317,6 +716,9 @@ pub struct Solution {
     raw: raw::Model,
     /// Cached column results to avoid creating a new slice on every access.
     col_solution: Box<[f64]>,
+    initial_solution_status: InitialSolutionStatus,
+    skipped: Vec<String>,
+    is_relaxation: bool,
 }
 impl Solution {
     /// Gets a shared reference to the internal `raw::Model`.
@@ -328,6 +730,26 @@ impl Solution {
         self.raw
     }
 
+    /// Returns whether this is the solution of the linear relaxation
+    /// of the model, given by `Model::solve_relaxation`.
+    pub fn is_relaxation(&self) -> bool {
+        self.is_relaxation
+    }
+
+    /// Gets what happened to the initial solution of the model.
+    pub fn initial_solution_status(&self) -> InitialSolutionStatus {
+        self.initial_solution_status
+    }
+
+    /// Gets why parts of the model were not given to CBC, which then
+    /// solved without them: the branching priorities when their file
+    /// cannot be written and, with the `dynamic-loading` feature, the
+    /// cut generators and the incumbent callback when the loaded libcbc
+    /// lacks the C interface of the CBC master branch.
+    pub fn skipped(&self) -> &[String] {
+        &self.skipped
+    }
+
     /// Gets the value of the given column in the solution.
     pub fn col(&self, col: Col) -> f64 {
         self.col_solution[col.as_usize()]
@@ -338,24 +760,27 @@ impl Solution {
         self.col(col) == 0.
     }
 
-    #[cfg(feature = "cbc-310")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
     /// Primal row solution : gets the value of the linear expression in the given constraint
     pub fn row_activity(&self, row: Row) -> f64 {
         self.raw.row_activity()[row.as_usize()]
     }
 
-    /* Disabled until getRowPrice is available in the C API
-    /// Dual column solution, or "shadow price":
+    #[cfg(cbc_master)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    /// Dual row solution, or "shadow price":
     /// the amount by which the optimal objective value is improved
     /// if the right-hand side of the given constraint is increased by 1.
+    ///
+    /// Only meaningful for linear problems: for a MILP, see
+    /// `Model::solve_with_duals`.
     pub fn row_price(&self, row: Row) -> f64 {
         self.raw.row_price()[row.as_usize()]
     }
-    */
 
-    #[cfg(feature = "cbc-310")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
     /// For a minimization problem, the reduced cost of a nonbasic variable
     /// (a variable that is null in the solution) is the amount by which the value of
     /// the objective will decrease if we increase the value of the variable by 1
@@ -364,6 +789,18 @@ impl Solution {
     }
 }
 
+/// Checks that libcbc provides the C function `name` of libcbc >= 2.10
+/// or of the CBC master branch, which may be missing from the library
+/// loaded at runtime with the `dynamic-loading` feature.
+#[cfg(all(cbc_ge_2_10, feature = "dynamic-loading"))]
+pub(crate) fn has_cbc_function(name: &str) -> bool {
+    raw::Model::is_available(name)
+}
+#[cfg(all(cbc_ge_2_10, not(feature = "dynamic-loading")))]
+pub(crate) fn has_cbc_function(_name: &str) -> bool {
+    true
+}
+
 /// Returns a tuple of (major, minor, patch) version of the libcbc installed on the current system
 pub fn libcbc_version() -> (u32, u32, u32) {
     let mut iter = raw::Model::version()
@@ -395,10 +832,33 @@ pub fn assert_min_libcbc_version(major: u32, minor: u32) {
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod test {
     use super::*;
     use crate::raw::{SecondaryStatus, Status};
 
+    /// The knapsack problem shared by the tests:
+    /// maximize 5x1 + 3x2 + 2x3 + 7x4 + 4x5
+    /// s.t.     2x1 + 8x2 + 4x3 + 2x4 + 5x5 <= 10
+    /// whose optimum is 16, with x1 = x4 = x5 = 1.
+    pub(crate) fn knapsack_model() -> (Model, Vec<Col>) {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let cols: Vec<Col> = (0..5).map(|_| m.add_binary()).collect();
+        let row = m.add_row();
+        m.set_row_upper(row, 10.);
+        for ((&col, &obj), &weight) in cols
+            .iter()
+            .zip(&[5., 3., 2., 7., 4.])
+            .zip(&[2., 8., 4., 2., 5.])
+        {
+            m.set_obj_coeff(col, obj);
+            m.set_weight(row, col, weight);
+        }
+        m.set_obj_sense(Sense::Maximize);
+        (m, cols)
+    }
+
     #[test]
     fn knapsack() {
         let mut m = Model::default();
@@ -434,6 +894,24 @@ mod test {
         assert_eq!(1., sol.col(cols[4]));
     }
 
+    #[test]
+    fn relaxation() {
+        let (mut m, cols) = knapsack_model();
+        m.add_sos1(vec![(cols[0], 1.), (cols[3], 2.)]);
+
+        let relaxed = m.solve_relaxation();
+        assert!(relaxed.is_relaxation());
+        assert!(relaxed.raw().is_proven_optimal());
+        // Without the SOS constraint, half of the third item fits
+        assert!((relaxed.raw().obj_value() - 16.5).abs() < 1e-6);
+        assert!((relaxed.col(cols[2]) - 0.25).abs() < 1e-6);
+
+        // The model still has its integer columns and SOS constraint
+        let sol = m.solve();
+        assert!(!sol.is_relaxation());
+        assert_eq!(11., sol.raw().obj_value());
+    }
+
     #[test]
     fn parallel_solves() {
         // Solve many instances of the knapsack test above, in parallel
@@ -466,7 +944,7 @@ mod test {
         assert!(!m.to_raw().is_proven_optimal());
     }
 
-    #[cfg(feature = "cbc-310")]
+    #[cfg(cbc_master)]
     #[test]
     fn simple() {
         // Formulate an infeasible problem and try to solve it
@@ -498,7 +976,186 @@ mod test {
         assert_eq!(solution.reduced_cost(y), -0.5);
 
         // If 2x + 3y == 9, we will have x=9/2 and the objective value will be 4.5 instead of 4
-        //assert_eq!(solution.row_price(c1), 0.5);
+        assert_eq!(solution.row_price(c1), 0.5);
+    }
+
+    #[cfg(cbc_master)]
+    #[test]
+    fn duals_of_milp() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_integer();
+        let y = m.add_col();
+
+        // Maximise 3x + y
+        m.set_obj_coeff(x, 3.);
+        m.set_obj_coeff(y, 1.);
+        m.set_obj_sense(Sense::Maximize);
+
+        // c1: 2x + y <= 5.5
+        let c1 = m.add_row();
+        m.set_weight(c1, x, 2.);
+        m.set_weight(c1, y, 1.);
+        m.set_row_upper(c1, 5.5);
+
+        let solution = m.solve_with_duals();
+        assert_eq!(solution.col(x), 2.);
+        assert_eq!(solution.col(y), 1.5);
+        // With x fixed to 2, one more unit of c1 gives one more unit of y
+        assert_eq!(solution.row_price(c1), 1.);
+    }
+
+    #[cfg(cbc_ge_2_10)]
+    #[test]
+    fn fixed_integers() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_integer();
+        let y = m.add_col();
+        m.set_col_upper(x, 10.);
+
+        // Minimize 2x + 3y
+        m.set_obj_coeff(x, 2.);
+        m.set_obj_coeff(y, 3.);
+        m.set_obj_sense(Sense::Minimize);
+
+        // c1: x + y >= 3.5
+        let c1 = m.add_row();
+        m.set_weight(c1, x, 1.);
+        m.set_weight(c1, y, 1.);
+        m.set_row_lower(c1, 3.5);
+
+        let milp = m.solve();
+        assert_eq!(milp.col(x), 3.);
+
+        let lp = m.with_fixed_integers(&milp);
+        assert_eq!(m.num_cols(), lp.num_cols());
+        assert!(lp.is_integer.iter().all(|&is_int| !is_int));
+        assert_eq!(3., lp.col_lower[x.as_usize()]);
+        assert_eq!(3., lp.col_upper[x.as_usize()]);
+        // The original model is unchanged
+        assert!(m.is_integer[x.as_usize()]);
+
+        let solution = m.solve_fixed_integers(&milp);
+        assert_eq!(solution.col(y), 0.5);
+        assert_eq!(solution.row_activity(c1), 3.5);
+        #[cfg(cbc_master)]
+        assert_eq!(solution.row_price(c1), 3.);
+        assert_eq!(solution.reduced_cost(y), 0.);
+    }
+
+    #[test]
+    fn partial_initial_solution() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let row = m.add_row();
+        m.set_row_upper(row, 10.);
+        let cols = [m.add_binary(), m.add_binary(), m.add_binary()];
+        m.set_weight(row, cols[0], 2.);
+        m.set_weight(row, cols[1], 8.);
+        m.set_weight(row, cols[2], 4.);
+        m.set_obj_coeff(cols[0], 5.);
+        m.set_obj_coeff(cols[1], 3.);
+        m.set_obj_coeff(cols[2], 2.);
+        m.set_obj_sense(Sense::Maximize);
+
+        // Only hint the second column, the others are left free
+        m.set_col_initial_solution(cols[1], 1.);
+        assert_eq!(None, m.get_col_initial_solution(cols[0]));
+        assert_eq!(Some(1.), m.get_col_initial_solution(cols[1]));
+        let sol = m.solve();
+        if completes_partial_solutions() {
+            assert_eq!(
+                InitialSolutionStatus::Completed,
+                sol.initial_solution_status()
+            );
+        } else {
+            assert_eq!(
+                InitialSolutionStatus::Partial,
+                sol.initial_solution_status()
+            );
+        }
+        assert_eq!(8., sol.raw().obj_value());
+
+        // A column added afterwards is not part of the initial solution
+        let extra = m.add_binary();
+        assert_eq!(None, m.get_col_initial_solution(extra));
+    }
+
+    #[test]
+    fn multiple_initial_solutions() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let row = m.add_row();
+        m.set_row_upper(row, 10.);
+        let cols = [m.add_binary(), m.add_binary(), m.add_binary()];
+        m.set_weight(row, cols[0], 2.);
+        m.set_weight(row, cols[1], 8.);
+        m.set_weight(row, cols[2], 4.);
+        m.set_obj_coeff(cols[0], 5.);
+        m.set_obj_coeff(cols[1], 3.);
+        m.set_obj_coeff(cols[2], 2.);
+        m.set_obj_sense(Sense::Maximize);
+
+        // Infeasible: 2 + 8 + 4 > 10
+        m.add_initial_solution(vec![(cols[0], 1.), (cols[1], 1.), (cols[2], 1.)]);
+        // Feasible, objective 7
+        m.add_initial_solution(vec![(cols[0], 1.), (cols[1], 0.), (cols[2], 1.)]);
+        // Feasible, objective 8
+        m.add_initial_solution(vec![(cols[0], 1.), (cols[1], 1.), (cols[2], 0.)]);
+        assert_eq!(3, m.num_initial_solutions());
+        assert_eq!(Some(1.), m.get_col_initial_solution(cols[2]));
+
+        let sol = m.solve();
+        assert_eq!(
+            InitialSolutionStatus::Feasible,
+            sol.initial_solution_status()
+        );
+        assert_eq!(8., sol.raw().obj_value());
+
+        m.remove_initial_solution();
+        assert_eq!(0, m.num_initial_solutions());
+        m.add_initial_solution(vec![(cols[0], 1.), (cols[1], 1.), (cols[2], 1.)]);
+        assert_eq!(
+            InitialSolutionStatus::Rejected,
+            m.solve().initial_solution_status()
+        );
+    }
+
+    /// Whether the partial initial solutions are completed before
+    /// being given to CBC, instead of being given as is.
+    #[cfg(cbc_ge_2_10)]
+    fn completes_partial_solutions() -> bool {
+        !has_cbc_function("Cbc_setMIPStartI")
+    }
+    #[cfg(not(cbc_ge_2_10))]
+    fn completes_partial_solutions() -> bool {
+        true
+    }
+
+    #[test]
+    fn rejected_initial_solution() {
+        if !completes_partial_solutions() {
+            return;
+        }
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let row = m.add_row();
+        m.set_row_upper(row, 1.);
+        let cols = [m.add_binary(), m.add_binary(), m.add_binary()];
+        for &col in &cols {
+            m.set_weight(row, col, 1.);
+        }
+        m.set_obj_sense(Sense::Maximize);
+        // x0 + x1 + x2 <= 1 cannot be completed when x0 = x1 = 1
+        m.set_col_initial_solution(cols[0], 1.);
+        m.set_col_initial_solution(cols[1], 1.);
+        let sol = m.solve();
+        assert_eq!(
+            InitialSolutionStatus::Rejected,
+            sol.initial_solution_status()
+        );
+        assert!(sol.raw().is_proven_optimal());
     }
 
     #[test]
@@ -0,0 +1,174 @@
+//! Logical constraints over binary columns.
+//!
+//! The columns given to these helpers must be binary, e.g. added with
+//! `Model::add_binary`; the helpers panic otherwise.
+
+use crate::{Col, ConstraintSense, Model, Row};
+
+impl Model {
+    /// Returns whether the column is an integer column restricted to
+    /// [0, 1].
+    pub(crate) fn is_binary(&self, col: Col) -> bool {
+        let i = col.as_usize();
+        self.is_integer[i] && self.col_lower[i] >= 0. && self.col_upper[i] <= 1.
+    }
+    fn assert_binary(&self, cols: &[Col]) {
+        for &col in cols {
+            assert!(self.is_binary(col), "column {} is not binary", col.0);
+        }
+    }
+    /// Adds a binary column equal to the conjunction of the given
+    /// binary columns, and returns it.
+    ///
+    /// Emits the rows `y <= x_i` and `y >= sum(x_i) - (n - 1)`.
+    pub fn add_and(&mut self, cols: &[Col]) -> Col {
+        self.assert_binary(cols);
+        let y = self.add_binary();
+        for &x in cols {
+            self.add_constraint(vec![(y, 1.), (x, -1.)], ConstraintSense::LessOrEqual, 0.);
+        }
+        let sum = cols.iter().map(|&x| (x, -1.));
+        self.add_constraint(
+            sum.chain(Some((y, 1.))),
+            ConstraintSense::GreaterOrEqual,
+            1. - cols.len() as f64,
+        );
+        y
+    }
+    /// Adds a binary column equal to the disjunction of the given
+    /// binary columns, and returns it.
+    ///
+    /// Emits the rows `y >= x_i` and `y <= sum(x_i)`.
+    pub fn add_or(&mut self, cols: &[Col]) -> Col {
+        self.assert_binary(cols);
+        let y = self.add_binary();
+        for &x in cols {
+            self.add_constraint(vec![(y, 1.), (x, -1.)], ConstraintSense::GreaterOrEqual, 0.);
+        }
+        let sum = cols.iter().map(|&x| (x, -1.));
+        self.add_constraint(sum.chain(Some((y, 1.))), ConstraintSense::LessOrEqual, 0.);
+        y
+    }
+    /// Adds a binary column equal to the negation of the given binary
+    /// column, and returns it.
+    ///
+    /// Emits the row `y + x = 1`.
+    pub fn add_not(&mut self, col: Col) -> Col {
+        self.assert_binary(&[col]);
+        let y = self.add_binary();
+        self.add_constraint(vec![(y, 1.), (col, 1.)], ConstraintSense::Equal, 1.);
+        y
+    }
+    /// Adds the implication `a => b` between two binary columns, and
+    /// returns its row `a - b <= 0`.
+    pub fn add_implies(&mut self, a: Col, b: Col) -> Row {
+        self.assert_binary(&[a, b]);
+        self.add_constraint(vec![(a, 1.), (b, -1.)], ConstraintSense::LessOrEqual, 0.)
+    }
+    /// Adds a binary column equal to the exclusive disjunction of two
+    /// binary columns, and returns it.
+    ///
+    /// Emits the rows `y >= a - b`, `y >= b - a`, `y <= a + b` and
+    /// `y <= 2 - a - b`.
+    pub fn add_xor(&mut self, a: Col, b: Col) -> Col {
+        self.assert_binary(&[a, b]);
+        let y = self.add_binary();
+        self.add_constraint(
+            vec![(y, 1.), (a, -1.), (b, 1.)],
+            ConstraintSense::GreaterOrEqual,
+            0.,
+        );
+        self.add_constraint(
+            vec![(y, 1.), (a, 1.), (b, -1.)],
+            ConstraintSense::GreaterOrEqual,
+            0.,
+        );
+        self.add_constraint(
+            vec![(y, 1.), (a, -1.), (b, -1.)],
+            ConstraintSense::LessOrEqual,
+            0.,
+        );
+        self.add_constraint(
+            vec![(y, 1.), (a, 1.), (b, 1.)],
+            ConstraintSense::LessOrEqual,
+            2.,
+        );
+        y
+    }
+    /// Adds the constraint that at most `k` of the given binary
+    /// columns are 1, and returns its row `sum(x_i) <= k`.
+    pub fn add_at_most_k(&mut self, cols: &[Col], k: u32) -> Row {
+        self.assert_binary(cols);
+        let sum = cols.iter().map(|&x| (x, 1.));
+        self.add_constraint(sum, ConstraintSense::LessOrEqual, k.into())
+    }
+    /// Adds the constraint that exactly one of the given binary
+    /// columns is 1, and returns its row `sum(x_i) = 1`.
+    pub fn add_exactly_one(&mut self, cols: &[Col]) -> Row {
+        self.assert_binary(cols);
+        let sum = cols.iter().map(|&x| (x, 1.));
+        self.add_constraint(sum, ConstraintSense::Equal, 1.)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Solves with the given columns fixed, and returns the value of `y`.
+    fn eval(m: &Model, fixed: &[(Col, f64)], y: Col) -> f64 {
+        let mut m = m.clone();
+        for &(col, value) in fixed {
+            m.set_col_lower(col, value);
+            m.set_col_upper(col, value);
+        }
+        let sol = m.solve();
+        assert!(sol.raw().is_proven_optimal());
+        sol.col(y)
+    }
+
+    #[test]
+    fn truth_tables() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let a = m.add_binary();
+        let b = m.add_binary();
+        let and = m.add_and(&[a, b]);
+        let or = m.add_or(&[a, b]);
+        let xor = m.add_xor(a, b);
+        let not = m.add_not(a);
+        for &(va, vb) in &[(0., 0.), (0., 1.), (1., 0.), (1., 1f64)] {
+            let fixed = [(a, va), (b, vb)];
+            assert_eq!(va.min(vb), eval(&m, &fixed, and));
+            assert_eq!(va.max(vb), eval(&m, &fixed, or));
+            assert_eq!((va - vb).abs(), eval(&m, &fixed, xor));
+            assert_eq!(1. - va, eval(&m, &fixed, not));
+        }
+    }
+
+    #[test]
+    fn cardinality() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let cols: Vec<Col> = (0..4).map(|_| m.add_binary()).collect();
+        m.add_at_most_k(&cols, 2);
+        m.add_exactly_one(&cols[2..]);
+        // cols[0] => cols[2]
+        m.add_implies(cols[0], cols[2]);
+        for (&col, weight) in cols.iter().zip(&[3., 2., 1., 4.]) {
+            m.set_obj_coeff(col, *weight);
+        }
+        m.set_obj_sense(crate::Sense::Maximize);
+        let sol = m.solve();
+        let values: Vec<f64> = cols.iter().map(|&c| sol.col(c)).collect();
+        assert_eq!(vec![0., 1., 0., 1.], values);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not binary")]
+    fn non_binary() {
+        let mut m = Model::default();
+        let x = m.add_integer();
+        m.add_not(x);
+    }
+}
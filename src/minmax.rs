@@ -0,0 +1,237 @@
+//! Minimum, maximum and absolute value of linear expressions.
+
+use crate::{Col, ConstraintSense, Model};
+
+/// The formulation of a minimum, maximum or absolute value column.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum MinMaxFormulation {
+    /// The column equals the function of the expressions, which is
+    /// enforced by binary columns with big-M coefficients derived from
+    /// the column bounds. The expressions must be bounded.
+    #[default]
+    Exact,
+    /// The column is only bounded by the expressions: it is greater
+    /// than or equal to a maximum or absolute value, lower than or
+    /// equal to a minimum. This needs no binary column, but is only
+    /// valid if the objective pushes the column towards the bound,
+    /// i.e. minimizes a maximum or absolute value, or maximizes a
+    /// minimum.
+    OneSided,
+}
+
+impl Model {
+    /// Adds a column equal to `|expr + constant|`, and returns it.
+    ///
+    /// Returns an error if the formulation is `MinMaxFormulation::Exact`
+    /// and the expression is unbounded.
+    pub fn add_abs(
+        &mut self,
+        expr: &[(Col, f64)],
+        constant: f64,
+        formulation: MinMaxFormulation,
+    ) -> Result<Col, String> {
+        let negated: Vec<(Col, f64)> = expr.iter().map(|&(col, coeff)| (col, -coeff)).collect();
+        self.add_extremum(&[(expr, constant), (&negated, -constant)], 1., formulation)
+    }
+    /// Adds a column equal to the maximum of the given affine
+    /// expressions, each one being a linear expression and a
+    /// constant, and returns it.
+    ///
+    /// Returns an error if the formulation is `MinMaxFormulation::Exact`
+    /// and an expression that can be the maximum is unbounded.
+    pub fn add_max(
+        &mut self,
+        exprs: &[(&[(Col, f64)], f64)],
+        formulation: MinMaxFormulation,
+    ) -> Result<Col, String> {
+        self.add_extremum(exprs, 1., formulation)
+    }
+    /// Adds a column equal to the minimum of the given affine
+    /// expressions, each one being a linear expression and a
+    /// constant, and returns it.
+    ///
+    /// Returns an error if the formulation is `MinMaxFormulation::Exact`
+    /// and an expression that can be the minimum is unbounded.
+    pub fn add_min(
+        &mut self,
+        exprs: &[(&[(Col, f64)], f64)],
+        formulation: MinMaxFormulation,
+    ) -> Result<Col, String> {
+        self.add_extremum(exprs, -1., formulation)
+    }
+    /// Adds `y = sign * max(sign * e_i)`: a maximum if `sign` is 1, a
+    /// minimum if it is -1.
+    ///
+    /// With `t = sign * y` and `f_i = sign * e_i`, the rows are
+    /// `t >= f_i`, and for the exact formulation `t <= f_i + M_i (1 - z_i)`
+    /// with `sum(z_i) = 1`. The expressions whose upper bound is below
+    /// the lower bound of another one can never be the maximum, and
+    /// are skipped.
+    fn add_extremum(
+        &mut self,
+        exprs: &[(&[(Col, f64)], f64)],
+        sign: f64,
+        formulation: MinMaxFormulation,
+    ) -> Result<Col, String> {
+        assert!(!exprs.is_empty(), "at least one expression is needed");
+        let bounds: Vec<(f64, f64)> = exprs
+            .iter()
+            .map(|&(expr, constant)| {
+                let (min, max) = self.expr_bounds(expr);
+                if sign > 0. {
+                    (min + constant, max + constant)
+                } else {
+                    (-max - constant, -min - constant)
+                }
+            })
+            .collect();
+        let t_lower = bounds.iter().map(|b| b.0).fold(f64::NEG_INFINITY, f64::max);
+        let candidates: Vec<usize> = (0..exprs.len())
+            .filter(|&i| bounds[i].1 >= t_lower)
+            .collect();
+        let t_upper = candidates
+            .iter()
+            .map(|&i| bounds[i].1)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let exact = formulation == MinMaxFormulation::Exact && candidates.len() > 1;
+        if exact && (t_upper.is_infinite() || candidates.iter().any(|&i| bounds[i].0.is_infinite()))
+        {
+            return Err("cannot derive a big-M: an expression is unbounded".to_string());
+        }
+
+        let y = self.add_col();
+        let (y_lower, y_upper) = match formulation {
+            MinMaxFormulation::Exact => (t_lower, t_upper),
+            MinMaxFormulation::OneSided => (t_lower, f64::INFINITY),
+        };
+        if sign > 0. {
+            self.set_col_lower(y, y_lower);
+            self.set_col_upper(y, y_upper);
+        } else {
+            self.set_col_lower(y, -y_upper);
+            self.set_col_upper(y, -y_lower);
+        }
+        // sign * (y - e_i) = sign * (y - expr_i) - sign * constant_i
+        let row_expr = |i: usize| {
+            let (expr, constant) = exprs[i];
+            let terms = expr.iter().map(move |&(col, coeff)| (col, -sign * coeff));
+            (terms.chain(Some((y, sign))), sign * constant)
+        };
+        if candidates.len() == 1 && formulation == MinMaxFormulation::Exact {
+            let (terms, rhs) = row_expr(candidates[0]);
+            self.add_constraint(terms, ConstraintSense::Equal, rhs);
+            return Ok(y);
+        }
+        let mut choices = vec![];
+        for &i in &candidates {
+            let (terms, rhs) = row_expr(i);
+            self.add_constraint(terms, ConstraintSense::GreaterOrEqual, rhs);
+            if exact {
+                // t - f_i + M_i z_i <= M_i
+                let m = t_upper - bounds[i].0;
+                let z = self.add_binary();
+                let (terms, rhs) = row_expr(i);
+                self.add_constraint(
+                    terms.chain(Some((z, m))),
+                    ConstraintSense::LessOrEqual,
+                    rhs + m,
+                );
+                choices.push((z, 1.));
+            }
+        }
+        if exact {
+            self.add_constraint(choices, ConstraintSense::Equal, 1.);
+        }
+        Ok(y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Sense;
+
+    #[test]
+    fn abs() {
+        for &formulation in &[MinMaxFormulation::Exact, MinMaxFormulation::OneSided] {
+            let mut m = Model::default();
+            m.set_parameter("log", "0");
+            let x = m.add_col();
+            m.set_col_upper(x, 10.);
+            m.add_constraint(vec![(x, 1.)], ConstraintSense::LessOrEqual, 4.);
+            // Minimize |x - 7|
+            let deviation = m.add_abs(&[(x, 1.)], -7., formulation).unwrap();
+            m.set_obj_coeff(deviation, 1.);
+            m.set_obj_sense(Sense::Minimize);
+            let sol = m.solve();
+            assert_eq!(4., sol.col(x));
+            assert_eq!(3., sol.col(deviation));
+        }
+    }
+
+    #[test]
+    fn exact_max() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_col();
+        m.set_col_upper(x, 4.);
+        let y = m.add_col();
+        m.set_col_upper(y, 6.);
+        // Maximize max(x, y + 1) - 2x - 2y
+        let max = m
+            .add_max(
+                &[(&[(x, 1.)], 0.), (&[(y, 1.)], 1.)],
+                MinMaxFormulation::Exact,
+            )
+            .unwrap();
+        m.set_obj_coeff(max, 1.);
+        m.set_obj_coeff(x, -2.);
+        m.set_obj_coeff(y, -2.);
+        m.set_obj_sense(Sense::Maximize);
+        let sol = m.solve();
+        assert_eq!(1., sol.col(max));
+        assert_eq!(0., sol.col(x));
+        assert_eq!(0., sol.col(y));
+    }
+
+    #[test]
+    fn exact_min() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_col();
+        m.set_col_lower(x, 2.);
+        m.set_col_upper(x, 5.);
+        let y = m.add_col();
+        m.set_col_upper(y, 8.);
+        // Minimize min(x, y) + x / 2
+        let min = m
+            .add_min(
+                &[(&[(x, 1.)], 0.), (&[(y, 1.)], 0.)],
+                MinMaxFormulation::Exact,
+            )
+            .unwrap();
+        m.set_obj_coeff(min, 1.);
+        m.set_obj_coeff(x, 0.5);
+        m.set_obj_sense(Sense::Minimize);
+        let sol = m.solve();
+        assert_eq!(0., sol.col(min));
+        assert_eq!(0., sol.col(y));
+        assert_eq!(2., sol.col(x));
+    }
+
+    #[test]
+    fn unbounded() {
+        let mut m = Model::default();
+        let x = m.add_col();
+        let y = m.add_col();
+        m.set_col_upper(y, 1.);
+        let exprs: &[(&[(Col, f64)], f64)] = &[(&[(x, 1.)], 0.), (&[(y, 1.)], 0.)];
+        assert!(m.add_max(exprs, MinMaxFormulation::Exact).is_err());
+        assert!(m.add_max(exprs, MinMaxFormulation::OneSided).is_ok());
+        // y - 2 is never the maximum
+        let exprs: &[(&[(Col, f64)], f64)] = &[(&[(y, 1.)], 3.), (&[(y, 1.)], -2.)];
+        let num_rows = m.num_rows();
+        assert!(m.add_max(exprs, MinMaxFormulation::Exact).is_ok());
+        assert_eq!(num_rows + 1, m.num_rows());
+    }
+}
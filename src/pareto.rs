@@ -0,0 +1,295 @@
+//! Pareto frontier of several objectives.
+
+use crate::{Col, ConstraintSense, Model, Row, Sense, Solution};
+
+/// Tolerance under which two objective values are considered equal.
+const OBJECTIVE_TOLERANCE: f64 = 1e-6;
+
+/// How `Model::pareto_frontier` explores the objectives.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParetoMethod {
+    /// Optimizes weighted sums of the objectives, the weights being
+    /// every combination of multiples of `1 / steps` summing to 1.
+    ///
+    /// The objectives are not normalized: the weights should be
+    /// meaningful for their scales. Only the points of the convex hull
+    /// of the frontier can be found.
+    WeightedSum {
+        /// The number of divisions of each weight.
+        steps: usize,
+    },
+    /// Optimizes the first objective, the other ones being bounded by
+    /// rows. The bounds sweep `steps + 1` values between the best and
+    /// the worst values of each objective among the optima of the
+    /// objectives.
+    EpsilonConstraint {
+        /// The number of divisions of the range of each bounded objective.
+        steps: usize,
+    },
+}
+
+/// A non-dominated solution found by `Model::pareto_frontier`.
+pub struct ParetoPoint {
+    solution: Solution,
+    objectives: Vec<f64>,
+}
+
+impl ParetoPoint {
+    /// Gets the solution.
+    pub fn solution(&self) -> &Solution {
+        &self.solution
+    }
+    /// Gets the value of each objective in the solution.
+    pub fn objectives(&self) -> &[f64] {
+        &self.objectives
+    }
+}
+
+impl Model {
+    /// Explores the trade-offs between the given objectives, and
+    /// returns the non-dominated solutions found, sorted by the value
+    /// of the first objective.
+    ///
+    /// The objectives are optimized in the sense of the model, as by
+    /// `Model::solve_lexicographic`. Each solve starts from the last
+    /// solution found. The solves that are not proven optimal are
+    /// ignored.
+    pub fn pareto_frontier(
+        &self,
+        objectives: &[&[(Col, f64)]],
+        method: ParetoMethod,
+    ) -> Vec<ParetoPoint> {
+        assert!(objectives.len() >= 2, "at least two objectives are needed");
+        let mut explorer = ParetoExplorer {
+            work: self.objective_work_model(),
+            objectives,
+            points: vec![],
+        };
+        match method {
+            ParetoMethod::WeightedSum { steps } => explorer.weighted_sum(steps),
+            ParetoMethod::EpsilonConstraint { steps } => explorer.epsilon_constraint(steps),
+        }
+        explorer.into_frontier()
+    }
+}
+
+struct ParetoExplorer<'a> {
+    work: Model,
+    objectives: &'a [&'a [(Col, f64)]],
+    points: Vec<ParetoPoint>,
+}
+
+impl ParetoExplorer<'_> {
+    /// Solves the work model with the given weights of the objectives,
+    /// and records the solution if it is optimal.
+    fn solve(&mut self, weights: &[f64]) -> Option<&ParetoPoint> {
+        let expr = self
+            .objectives
+            .iter()
+            .zip(weights)
+            .flat_map(|(obj, &w)| obj.iter().map(move |&(col, coeff)| (col, w * coeff)));
+        self.work.replace_objective(expr);
+        if let Some(previous) = self.points.last() {
+            self.work.remove_initial_solution();
+            self.work.set_initial_solution(&previous.solution);
+        }
+        let solution = self.work.solve();
+        if !solution.raw().is_proven_optimal() {
+            return None;
+        }
+        let objectives = self
+            .objectives
+            .iter()
+            .map(|obj| {
+                obj.iter()
+                    .map(|&(col, coeff)| coeff * solution.col(col))
+                    .sum()
+            })
+            .collect();
+        self.points.push(ParetoPoint {
+            solution,
+            objectives,
+        });
+        self.points.last()
+    }
+    fn weighted_sum(&mut self, steps: usize) {
+        for weights in simplex_grid(self.objectives.len(), steps.max(1)) {
+            self.solve(&weights);
+        }
+    }
+    fn epsilon_constraint(&mut self, steps: usize) {
+        let n = self.objectives.len();
+        // Optimum of each objective, ties being broken by the other ones
+        let mut anchors = vec![];
+        for j in 0..n {
+            let mut weights = vec![OBJECTIVE_TOLERANCE; n];
+            weights[j] = 1.;
+            if let Some(point) = self.solve(&weights) {
+                anchors.push(point.objectives.clone());
+            }
+        }
+        if anchors.len() < n {
+            return;
+        }
+        let sign = self.sign();
+        // Bounds of the objectives 1..n, from their worst to their best value
+        let ranges: Vec<(f64, f64)> = (1..n)
+            .map(|k| {
+                let worst = anchors
+                    .iter()
+                    .map(|a| sign * a[k])
+                    .fold(f64::NEG_INFINITY, f64::max);
+                (sign * worst, anchors[k][k])
+            })
+            .collect();
+        let sense = if sign > 0. {
+            ConstraintSense::LessOrEqual
+        } else {
+            ConstraintSense::GreaterOrEqual
+        };
+        let rows: Vec<Row> = (1..n)
+            .map(|k| {
+                let expr = self.objectives[k].iter().copied();
+                self.work.add_constraint(expr, sense, ranges[k - 1].0)
+            })
+            .collect();
+        let mut weights = vec![0.; n];
+        weights[0] = 1.;
+        let steps = steps.max(1);
+        for grid_point in 0..(steps + 1).pow(n as u32 - 1) {
+            let mut index = grid_point;
+            for (&row, &(worst, best)) in rows.iter().zip(&ranges) {
+                let t = (index % (steps + 1)) as f64 / steps as f64;
+                index /= steps + 1;
+                let epsilon = worst + t * (best - worst);
+                if sign > 0. {
+                    self.work.set_row_upper(row, epsilon);
+                } else {
+                    self.work.set_row_lower(row, epsilon);
+                }
+            }
+            self.solve(&weights);
+        }
+    }
+    /// 1 if the objectives are minimized, -1 if they are maximized.
+    fn sign(&self) -> f64 {
+        match self.work.sense {
+            Sense::Maximize => -1.,
+            _ => 1.,
+        }
+    }
+    /// Removes the dominated and duplicate points.
+    fn into_frontier(self) -> Vec<ParetoPoint> {
+        let sign = self.sign();
+        // a dominates b if it is at least as good on every objective;
+        // equal points are deduplicated by keeping the first one
+        let dominates = |a: &[f64], b: &[f64]| {
+            a.iter()
+                .zip(b)
+                .all(|(x, y)| sign * x <= sign * y + OBJECTIVE_TOLERANCE)
+        };
+        let mut frontier: Vec<ParetoPoint> = vec![];
+        for point in self.points {
+            if frontier
+                .iter()
+                .any(|p| dominates(&p.objectives, &point.objectives))
+            {
+                continue;
+            }
+            frontier.retain(|p| !dominates(&point.objectives, &p.objectives));
+            frontier.push(point);
+        }
+        frontier.sort_by(|a, b| (sign * a.objectives[0]).total_cmp(&(sign * b.objectives[0])));
+        frontier
+    }
+}
+
+/// Gets every vector of `n` multiples of `1 / steps` summing to 1.
+fn simplex_grid(n: usize, steps: usize) -> Vec<Vec<f64>> {
+    fn fill(prefix: &mut Vec<usize>, n: usize, remaining: usize, grid: &mut Vec<Vec<usize>>) {
+        if prefix.len() + 1 == n {
+            prefix.push(remaining);
+            grid.push(prefix.clone());
+            prefix.pop();
+            return;
+        }
+        for k in 0..=remaining {
+            prefix.push(k);
+            fill(prefix, n, remaining - k, grid);
+            prefix.pop();
+        }
+    }
+    let mut grid = vec![];
+    fill(&mut vec![], n, steps, &mut grid);
+    grid.into_iter()
+        .map(|v| v.into_iter().map(|k| k as f64 / steps as f64).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid() {
+        let grid = simplex_grid(3, 2);
+        assert_eq!(6, grid.len());
+        assert!(grid.iter().all(|w| w.iter().sum::<f64>() == 1.));
+        assert_eq!(
+            vec![vec![0., 1.], vec![0.5, 0.5], vec![1., 0.]],
+            simplex_grid(2, 2)
+        );
+    }
+
+    type Objective = Vec<(Col, f64)>;
+
+    /// Cost vs. service: 3 binary orders cost 1, 3 and 4, and serve 1,
+    /// 2 and 4 units. The second objective is minus the served units.
+    fn cost_vs_service() -> (Model, Objective, Objective) {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let orders: Vec<Col> = (0..3).map(|_| m.add_binary()).collect();
+        let cost = orders.iter().zip(&[1., 3., 4.]).map(|(&c, &w)| (c, w));
+        let unserved = orders.iter().zip(&[-1., -2., -4.]).map(|(&c, &w)| (c, w));
+        m.set_obj_sense(Sense::Minimize);
+        (m, cost.collect(), unserved.collect())
+    }
+
+    #[test]
+    fn epsilon_constraint() {
+        let (m, cost, unserved) = cost_vs_service();
+        let frontier = m.pareto_frontier(
+            &[&cost, &unserved],
+            ParetoMethod::EpsilonConstraint { steps: 7 },
+        );
+        let objectives: Vec<&[f64]> = frontier.iter().map(|p| p.objectives()).collect();
+        // Orders 0 and 1 together, (4, -3), are dominated by order 2
+        assert_eq!(
+            vec![
+                &[0., 0.][..],
+                &[1., -1.],
+                &[3., -2.],
+                &[4., -4.],
+                &[5., -5.],
+                &[7., -6.],
+                &[8., -7.],
+            ],
+            objectives
+        );
+    }
+
+    #[test]
+    fn weighted_sum() {
+        let (m, cost, unserved) = cost_vs_service();
+        let frontier =
+            m.pareto_frontier(&[&cost, &unserved], ParetoMethod::WeightedSum { steps: 4 });
+        // Only supported points are found, and all are non-dominated
+        assert!(frontier.len() >= 2);
+        assert_eq!(&[0., 0.], frontier[0].objectives());
+        assert_eq!(&[8., -7.], frontier.last().unwrap().objectives());
+        for (a, b) in frontier.iter().zip(&frontier[1..]) {
+            assert!(a.objectives()[0] < b.objectives()[0]);
+            assert!(a.objectives()[1] > b.objectives()[1]);
+        }
+    }
+}
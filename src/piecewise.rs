@@ -0,0 +1,165 @@
+//! Piecewise-linear functions.
+
+use crate::{Col, ConstraintSense, Model};
+
+/// The formulation of a piecewise-linear function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum PiecewiseFormulation {
+    /// One weight column per breakpoint, in a SOS2 constraint.
+    #[default]
+    Sos2,
+    /// One fill column per segment, the segments being filled in
+    /// order as enforced by binary columns.
+    Incremental,
+}
+
+impl Model {
+    /// Adds a column `y = f(x)`, where `f` is the piecewise-linear
+    /// function going through the given `(x, y)` breakpoints, and
+    /// returns it. `x` is restricted to the range of the breakpoints.
+    ///
+    /// Uses the SOS2 formulation, see `add_piecewise_linear_with`.
+    pub fn add_piecewise_linear(&mut self, x: Col, breakpoints: &[(f64, f64)]) -> Col {
+        self.add_piecewise_linear_with(x, breakpoints, PiecewiseFormulation::Sos2)
+    }
+    /// Adds a column `y = f(x)`, where `f` is the piecewise-linear
+    /// function going through the given `(x, y)` breakpoints, using
+    /// the given formulation, and returns it.
+    ///
+    /// The breakpoints must be sorted by strictly increasing `x`,
+    /// and there must be at least two of them.
+    pub fn add_piecewise_linear_with(
+        &mut self,
+        x: Col,
+        breakpoints: &[(f64, f64)],
+        formulation: PiecewiseFormulation,
+    ) -> Col {
+        assert!(
+            breakpoints.len() >= 2,
+            "at least two breakpoints are needed"
+        );
+        assert!(
+            breakpoints.windows(2).all(|w| w[0].0 < w[1].0),
+            "the breakpoints must be sorted by strictly increasing x"
+        );
+        let y = self.add_col();
+        let (y_min, y_max) = breakpoints
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(_, y)| {
+                (min.min(y), max.max(y))
+            });
+        self.set_col_lower(y, y_min);
+        self.set_col_upper(y, y_max);
+        match formulation {
+            PiecewiseFormulation::Sos2 => self.add_piecewise_sos2(x, y, breakpoints),
+            PiecewiseFormulation::Incremental => self.add_piecewise_incremental(x, y, breakpoints),
+        }
+        y
+    }
+    /// x = sum(lambda_i x_i), y = sum(lambda_i y_i), sum(lambda_i) = 1,
+    /// with lambda in a SOS2 constraint.
+    fn add_piecewise_sos2(&mut self, x: Col, y: Col, breakpoints: &[(f64, f64)]) {
+        let lambdas: Vec<Col> = breakpoints
+            .iter()
+            .map(|_| {
+                let lambda = self.add_col();
+                self.set_col_upper(lambda, 1.);
+                lambda
+            })
+            .collect();
+        self.add_constraint(lambdas.iter().map(|&l| (l, 1.)), ConstraintSense::Equal, 1.);
+        let x_expr = lambdas
+            .iter()
+            .zip(breakpoints)
+            .map(|(&l, &(bx, _))| (l, bx));
+        self.add_constraint(x_expr.chain(Some((x, -1.))), ConstraintSense::Equal, 0.);
+        let y_expr = lambdas
+            .iter()
+            .zip(breakpoints)
+            .map(|(&l, &(_, by))| (l, by));
+        self.add_constraint(y_expr.chain(Some((y, -1.))), ConstraintSense::Equal, 0.);
+        self.add_sos2(
+            lambdas
+                .iter()
+                .zip(breakpoints)
+                .map(|(&l, &(bx, _))| (l, bx)),
+        );
+    }
+    /// x = x_0 + sum(delta_k dx_k), y = y_0 + sum(delta_k dy_k), with
+    /// delta_{k+1} <= z_k <= delta_k for binary z_k.
+    fn add_piecewise_incremental(&mut self, x: Col, y: Col, breakpoints: &[(f64, f64)]) {
+        let deltas: Vec<Col> = breakpoints
+            .windows(2)
+            .map(|_| {
+                let delta = self.add_col();
+                self.set_col_upper(delta, 1.);
+                delta
+            })
+            .collect();
+        let (x0, y0) = breakpoints[0];
+        let x_expr = deltas
+            .iter()
+            .zip(breakpoints.windows(2))
+            .map(|(&d, w)| (d, w[1].0 - w[0].0));
+        self.add_constraint(x_expr.chain(Some((x, -1.))), ConstraintSense::Equal, -x0);
+        let y_expr = deltas
+            .iter()
+            .zip(breakpoints.windows(2))
+            .map(|(&d, w)| (d, w[1].1 - w[0].1));
+        self.add_constraint(y_expr.chain(Some((y, -1.))), ConstraintSense::Equal, -y0);
+        for pair in deltas.windows(2) {
+            let z = self.add_binary();
+            self.add_constraint(
+                vec![(pair[1], 1.), (z, -1.)],
+                ConstraintSense::LessOrEqual,
+                0.,
+            );
+            self.add_constraint(
+                vec![(z, 1.), (pair[0], -1.)],
+                ConstraintSense::LessOrEqual,
+                0.,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Sense;
+
+    /// Tiered pricing: cost 2/unit up to 10 units, 1/unit up to 20,
+    /// then 3/unit up to 30.
+    fn tiered(formulation: PiecewiseFormulation, quantity: f64) -> f64 {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_col();
+        let cost = m.add_piecewise_linear_with(
+            x,
+            &[(0., 0.), (10., 20.), (20., 30.), (30., 60.)],
+            formulation,
+        );
+        let demand = m.add_row();
+        m.set_weight(demand, x, 1.);
+        m.set_row_lower(demand, quantity);
+        m.set_obj_coeff(cost, 1.);
+        m.set_obj_sense(Sense::Minimize);
+        let sol = m.solve();
+        assert!(sol.raw().is_proven_optimal());
+        assert!((sol.col(x) - quantity).abs() < 1e-6);
+        sol.col(cost)
+    }
+
+    #[test]
+    fn sos2() {
+        assert!((tiered(PiecewiseFormulation::Sos2, 15.) - 25.).abs() < 1e-6);
+        assert!((tiered(PiecewiseFormulation::Sos2, 25.) - 45.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn incremental() {
+        // The non-convex cost requires the segments to be filled in order
+        assert!((tiered(PiecewiseFormulation::Incremental, 15.) - 25.).abs() < 1e-6);
+        assert!((tiered(PiecewiseFormulation::Incremental, 25.) - 45.).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,154 @@
+//! Enumeration of alternative solutions with no-good rows.
+
+use crate::{Col, ConstraintSense, Model, Sense, Solution};
+
+impl Model {
+    /// Finds up to `max` distinct solutions whose objective value is
+    /// within the relative `gap` of the optimum, best first.
+    ///
+    /// CBC keeps no pool of solutions: the model is solved repeatedly,
+    /// each solution being excluded by a "no-good" row on the integer
+    /// columns before the next solve. Thus, the solutions differ on
+    /// the values of their integer columns; general integer columns
+    /// are expanded into binary columns for the no-good rows. If the
+    /// objective sense is `Sense::Ignore`, any feasible solutions are
+    /// enumerated.
+    ///
+    /// Returns an error if an integer column that is not binary has an
+    /// infinite bound.
+    pub fn enumerate_solutions(&self, max: usize, gap: f64) -> Result<Vec<Solution>, String> {
+        let mut work = self.clone();
+        work.remove_initial_solution();
+        let bits = work.binary_expansion()?;
+        let mut solutions: Vec<Solution> = vec![];
+        while solutions.len() < max {
+            let solution = work.solve();
+            if !solution.raw().is_proven_optimal() {
+                break;
+            }
+            if solutions.is_empty() {
+                // Restrict the next solves to the objective window
+                let best = solution.raw().obj_value();
+                let objective = work.objective_expr();
+                match work.sense {
+                    Sense::Minimize => {
+                        work.add_constraint(
+                            objective,
+                            ConstraintSense::LessOrEqual,
+                            best + gap * best.abs(),
+                        );
+                    }
+                    Sense::Maximize => {
+                        work.add_constraint(
+                            objective,
+                            ConstraintSense::GreaterOrEqual,
+                            best - gap * best.abs(),
+                        );
+                    }
+                    Sense::Ignore => {}
+                }
+            }
+            // sum(1 - b, b = 1) + sum(b, b = 0) >= 1
+            let mut ones = 0.;
+            let no_good: Vec<(Col, f64)> = bits
+                .iter()
+                .map(|&b| {
+                    if solution.col(b) > 0.5 {
+                        ones += 1.;
+                        (b, -1.)
+                    } else {
+                        (b, 1.)
+                    }
+                })
+                .collect();
+            work.add_constraint(no_good, ConstraintSense::GreaterOrEqual, 1. - ones);
+            solutions.push(solution);
+        }
+        Ok(solutions)
+    }
+    /// Gets the binary columns describing the values of the integer
+    /// columns: the binary columns themselves, and for the general
+    /// integer columns `x` in `[l, u]`, new binary columns `b_k` with
+    /// `x = l + sum(2^k b_k)`.
+    fn binary_expansion(&mut self) -> Result<Vec<Col>, String> {
+        let mut bits = vec![];
+        for col in self.cols().collect::<Vec<_>>() {
+            let i = col.as_usize();
+            if !self.is_integer[i] {
+                continue;
+            }
+            if self.is_binary(col) {
+                bits.push(col);
+                continue;
+            }
+            let lower = self.col_lower[i].ceil();
+            let upper = self.col_upper[i].floor();
+            if !lower.is_finite() || !upper.is_finite() {
+                return Err(format!(
+                    "cannot enumerate the values of the unbounded integer column {}",
+                    col.0
+                ));
+            }
+            let mut expansion = vec![(col, 1.)];
+            let mut weight = 1.;
+            while weight <= upper - lower {
+                let b = self.add_binary();
+                expansion.push((b, -weight));
+                bits.push(b);
+                weight *= 2.;
+            }
+            self.add_constraint(expansion, ConstraintSense::Equal, lower);
+        }
+        Ok(bits)
+    }
+    /// Gets the objective as a linear expression.
+    fn objective_expr(&self) -> Vec<(Col, f64)> {
+        self.cols()
+            .map(|col| (col, self.obj_coefficients[col.as_usize()]))
+            .filter(|&(_, coeff)| coeff != 0.)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::knapsack_model;
+
+    #[test]
+    fn within_gap() {
+        let (m, items) = knapsack_model();
+        // The objective must be at least 16 - 0.2 * 16
+        let solutions = m.enumerate_solutions(10, 0.2).unwrap();
+        let values: Vec<Vec<f64>> = solutions
+            .iter()
+            .map(|s| items.iter().map(|&c| s.col(c)).collect())
+            .collect();
+        assert_eq!(
+            vec![vec![1., 0., 0., 1., 1.], vec![1., 0., 1., 1., 0.]],
+            values
+        );
+        assert_eq!(14., solutions[1].raw().obj_value());
+        assert_eq!(3, m.enumerate_solutions(3, 1.).unwrap().len());
+    }
+
+    #[test]
+    fn general_integers() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_integer();
+        m.set_col_lower(x, 1.);
+        m.set_col_upper(x, 4.);
+        let mut values: Vec<f64> = m
+            .enumerate_solutions(10, 0.)
+            .unwrap()
+            .iter()
+            .map(|s| s.col(x))
+            .collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(vec![1., 2., 3., 4.], values);
+
+        m.set_col_upper(x, f64::INFINITY);
+        assert!(m.enumerate_solutions(10, 0.).is_err());
+    }
+}
@@ -10,8 +10,15 @@
 
 use coin_cbc_sys::*;
 use std::convert::TryInto;
-use std::ffi::CStr;
-use std::os::raw::c_int;
+use std::ffi::{CStr, CString};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::os::raw::{c_char, c_int};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+#[cfg(cbc_master)]
+use std::{any::Any, collections::HashMap, os::raw::c_void, panic, sync::Mutex};
 
 #[cfg(feature = "singlethread-cbc")]
 fn lock<T, F: FnOnce() -> T>(f: F) -> T {
@@ -28,7 +35,7 @@ fn lock<T, F: FnOnce() -> T>(f: F) -> T {
 }
 
 /// Sense of optimization.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub enum Sense {
     /// Objective must be minimized.
     Minimize,
@@ -36,13 +43,9 @@ pub enum Sense {
     Maximize,
     /// The objective is ignored, only searching for a feasible
     /// solution.
+    #[default]
     Ignore,
 }
-impl Default for Sense {
-    fn default() -> Self {
-        Sense::Ignore
-    }
-}
 
 /// Status of the model.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -74,6 +77,66 @@ pub enum SecondaryStatus {
     StoppedOnIterationLimit = 8,
 }
 
+/// The preferred branching direction of a column.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BranchDirection {
+    /// Explore first the branch where the column is rounded up.
+    Up,
+    /// Explore first the branch where the column is rounded down.
+    Down,
+}
+
+/// A temporary file, removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Creates a new file in the temporary directory. The file must
+    /// not exist, so that an existing file or symbolic link with the
+    /// same name is never written to.
+    fn create(prefix: &str, contents: &str) -> std::io::Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        loop {
+            let name = format!(
+                "{}_{}_{}.csv",
+                prefix,
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            );
+            let path = std::env::temp_dir().join(name);
+            let file = OpenOptions::new().write(true).create_new(true).open(&path);
+            match file {
+                Ok(mut file) => {
+                    let file_path = TempFile(path);
+                    file.write_all(contents.as_bytes())?;
+                    return Ok(file_path);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Gets the contents of a CBC `priorityIn` file.
+fn priority_file_contents(priorities: &[(usize, c_int, Option<BranchDirection>)]) -> String {
+    let mut contents = String::from("number,priority,direction\n");
+    for &(col, priority, direction) in priorities {
+        let direction = match direction {
+            Some(BranchDirection::Up) => 'U',
+            Some(BranchDirection::Down) => 'D',
+            None => 'N',
+        };
+        contents += &format!("{},{},{}\n", col, priority, direction);
+    }
+    contents
+}
+
 /// The type of a special ordered set constraint
 #[repr(i32)]
 pub enum SOSConstraintType {
@@ -84,12 +147,153 @@ pub enum SOSConstraintType {
     Type2 = 2,
 }
 
+/// A cut callback: gets the column solution of the LP of the current
+/// node, and adds cuts to the `RowCuts`.
+#[cfg(cbc_master)]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+pub type CutCallback = dyn FnMut(&[f64], &mut RowCuts) + Send;
+
+/// The cuts generated by a `CutCallback`.
+#[cfg(cbc_master)]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+pub struct RowCuts {
+    cuts: *mut c_void,
+    num_cols: usize,
+}
+
+#[cfg(cbc_master)]
+#[allow(missing_docs)]
+impl RowCuts {
+    /// sense: 'L', 'G', 'E' or 'R'
+    /// Returns an error, without adding the cut, if `indices` and
+    /// `elements` have different lengths or an index is not a column
+    /// of the solver.
+    pub fn add_row_cut(
+        &mut self,
+        indices: &[c_int],
+        elements: &[f64],
+        sense: c_char,
+        rhs: f64,
+    ) -> Result<(), String> {
+        if indices.len() != elements.len() {
+            return Err(format!(
+                "{} indices for {} elements",
+                indices.len(),
+                elements.len()
+            ));
+        }
+        let num_cols = self.num_cols;
+        if let Some(i) = indices.iter().find(|&&i| i < 0 || i as usize >= num_cols) {
+            return Err(format!("{} is not one of the {} columns", i, num_cols));
+        }
+        unsafe {
+            OsiCuts_addRowCut(
+                self.cuts,
+                indices.len().try_into().unwrap(),
+                indices.as_ptr(),
+                elements.as_ptr(),
+                sense,
+                rhs,
+            )
+        };
+        Ok(())
+    }
+}
+
+/// The payload of a panic, caught before it unwinds into CBC.
+#[cfg(cbc_master)]
+type Panic = Box<dyn Any + Send>;
+
+#[cfg(cbc_master)]
+struct CutCallbackData {
+    callback: Box<CutCallback>,
+    /// The panic of the callback, resumed once CBC returns.
+    panic: Option<Panic>,
+}
+
+#[cfg(cbc_master)]
+unsafe extern "C" fn cut_callback_trampoline(
+    osi_solver: *mut c_void,
+    osi_cuts: *mut c_void,
+    app_data: *mut c_void,
+) {
+    let data = &*(app_data as *const Mutex<CutCallbackData>);
+    let mut data = data.lock().unwrap_or_else(|e| e.into_inner());
+    if data.panic.is_some() {
+        return;
+    }
+    let callback = &mut data.callback;
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let num_cols = Osi_getNumCols(osi_solver).try_into().unwrap();
+        let col_solution = std::slice::from_raw_parts(Osi_getColSolution(osi_solver), num_cols);
+        let mut cuts = RowCuts {
+            cuts: osi_cuts,
+            num_cols,
+        };
+        callback(col_solution, &mut cuts);
+    }));
+    data.panic = result.err();
+}
+
+/// An incumbent callback: gets the objective value and the column
+/// values of each new best solution.
+#[cfg(cbc_master)]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+pub type IncumbentCallback = dyn FnMut(f64, Vec<f64>) + Send;
+
+#[cfg(cbc_master)]
+struct IncumbentCallbackData {
+    num_cols: usize,
+    col_indices: HashMap<CString, usize>,
+    callback: Box<IncumbentCallback>,
+    /// The panic of the callback, resumed once CBC returns.
+    panic: Option<Panic>,
+}
+
+#[cfg(cbc_master)]
+unsafe extern "C" fn incumbent_callback_trampoline(
+    _cbc_model: *mut c_void,
+    obj: f64,
+    nz: c_int,
+    names: *mut *mut c_char,
+    x: *mut f64,
+    app_data: *mut c_void,
+) -> c_int {
+    let data = &*(app_data as *const Mutex<IncumbentCallbackData>);
+    let mut data = data.lock().unwrap_or_else(|e| e.into_inner());
+    if data.panic.is_some() {
+        return 1;
+    }
+    let data = &mut *data;
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let mut values = vec![0.; data.num_cols];
+        for k in 0..nz.try_into().unwrap() {
+            let name = CStr::from_ptr(*names.add(k));
+            if let Some(&i) = data.col_indices.get(name) {
+                values[i] = *x.add(k);
+            }
+        }
+        (data.callback)(obj, values);
+    }));
+    data.panic = result.err();
+    1
+}
+
 /// A CBC MILP model.
 ///
 /// Their methods are a direct translation from the C API. For
 /// documentation, see the official API documentation.
 pub struct Model {
     m: *mut Cbc_Model,
+    /// The file of the branching priorities given to CBC, which must
+    /// exist until solving.
+    priority_file: Option<Arc<TempFile>>,
+    /// The data of the cut callbacks, which must live as long as the
+    /// model and its clones.
+    #[cfg(cbc_master)]
+    cut_callbacks: Vec<Arc<Mutex<CutCallbackData>>>,
+    #[cfg(cbc_master)]
+    incumbent_callback: Option<Arc<Mutex<IncumbentCallbackData>>>,
 }
 
 #[allow(missing_docs)]
@@ -97,11 +301,34 @@ impl Model {
     pub fn new() -> Self {
         Self {
             m: lock(|| unsafe { Cbc_newModel() }),
+            priority_file: None,
+            #[cfg(cbc_master)]
+            cut_callbacks: vec![],
+            #[cfg(cbc_master)]
+            incumbent_callback: None,
         }
     }
     pub fn version() -> &'static str {
         unsafe { CStr::from_ptr(Cbc_getVersion()).to_str().unwrap() }
     }
+    #[cfg(feature = "dynamic-loading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dynamic-loading")))]
+    /// Loads libCbcSolver and creates a model, or returns the loading
+    /// error, on which `Model::new` panics.
+    pub fn try_new() -> Result<Self, String> {
+        coin_cbc_sys::load()?;
+        Ok(Self::new())
+    }
+    #[cfg(feature = "dynamic-loading")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dynamic-loading")))]
+    /// Checks that the loaded libCbcSolver provides the C function
+    /// `name`, e.g. `"Cbc_getRowActivity"`. The methods calling a
+    /// missing function panic with "function unavailable in this
+    /// libcbc".
+    pub fn is_available(name: &str) -> bool {
+        coin_cbc_sys::is_available(name)
+    }
+    #[allow(clippy::too_many_arguments, clippy::unnecessary_map_or)]
     pub fn load_problem(
         &mut self,
         numcols: usize,
@@ -158,10 +385,59 @@ impl Model {
     pub fn write_mps(&self, filename: &CStr) {
         unsafe { Cbc_writeMps(self.m, filename.as_ptr()) };
     }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Reads a model in the LP format.
+    /// This function is not available on libcbc < 2.10.
+    pub fn read_lp(&mut self, filename: &CStr) {
+        unsafe { Cbc_readLp(self.m, filename.as_ptr()) };
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Writes the model in the LP format.
+    /// This function is not available on libcbc < 2.10.
+    pub fn write_lp(&self, filename: &CStr) {
+        unsafe { Cbc_writeLp(self.m, filename.as_ptr()) };
+    }
     pub fn set_initial_solution(&mut self, sol: &[f64]) {
         assert_eq!(self.num_cols(), sol.len());
         unsafe { Cbc_setInitialSolution(self.m, sol.as_ptr()) };
     }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets a possibly partial MIP start using column names.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_mip_start(&mut self, col_names: &[&CStr], values: &[f64]) {
+        assert_eq!(col_names.len(), values.len());
+        let names: Vec<*const c_char> = col_names.iter().map(|n| n.as_ptr()).collect();
+        unsafe {
+            Cbc_setMIPStart(
+                self.m,
+                names.len().try_into().unwrap(),
+                names.as_ptr(),
+                values.as_ptr(),
+            )
+        };
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets a possibly partial MIP start using column indices.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_mip_start_i(&mut self, col_indices: &[c_int], values: &[f64]) {
+        assert_eq!(col_indices.len(), values.len());
+        let num_cols = self.num_cols();
+        assert!(col_indices
+            .iter()
+            .all(|&i| i >= 0 && (i as usize) < num_cols));
+        unsafe {
+            Cbc_setMIPStartI(
+                self.m,
+                col_indices.len().try_into().unwrap(),
+                col_indices.as_ptr(),
+                values.as_ptr(),
+            )
+        };
+    }
     // TODO: setProblemName
     pub fn num_elements(&self) -> usize {
         unsafe { Cbc_getNumElements(self.m).try_into().unwrap() }
@@ -183,9 +459,47 @@ impl Model {
         unsafe { std::slice::from_raw_parts(Cbc_getElements(self.m), size) }
     }
     pub fn max_name_length(&self) -> usize {
-        unsafe { Cbc_maxNameLength(self.m).try_into().unwrap() }
+        unsafe { Cbc_maxNameLength(self.m) }
+    }
+    /// Buffer large enough for any name, including the default ones
+    /// generated by CBC for unnamed rows and columns.
+    fn name_buffer(&self) -> Vec<c_char> {
+        vec![0; self.max_name_length().max(32) + 1]
+    }
+    pub fn row_name(&self, i: usize) -> CString {
+        assert!(i < self.num_rows());
+        let mut name = self.name_buffer();
+        unsafe {
+            Cbc_getRowName(
+                self.m,
+                i.try_into().unwrap(),
+                name.as_mut_ptr(),
+                name.len() - 1,
+            );
+            CStr::from_ptr(name.as_ptr()).to_owned()
+        }
+    }
+    pub fn col_name(&self, i: usize) -> CString {
+        assert!(i < self.num_cols());
+        let mut name = self.name_buffer();
+        unsafe {
+            Cbc_getColName(
+                self.m,
+                i.try_into().unwrap(),
+                name.as_mut_ptr(),
+                name.len() - 1,
+            );
+            CStr::from_ptr(name.as_ptr()).to_owned()
+        }
+    }
+    pub fn set_row_name(&mut self, i: usize, name: &CStr) {
+        assert!(i < self.num_rows());
+        unsafe { Cbc_setRowName(self.m, i.try_into().unwrap(), name.as_ptr()) }
+    }
+    pub fn set_col_name(&mut self, i: usize, name: &CStr) {
+        assert!(i < self.num_cols());
+        unsafe { Cbc_setColName(self.m, i.try_into().unwrap(), name.as_ptr()) }
     }
-    // TODO: name management
     pub fn num_rows(&self) -> usize {
         unsafe { Cbc_getNumRows(self.m).try_into().unwrap() }
     }
@@ -262,6 +576,168 @@ impl Model {
         assert!(i < self.num_cols());
         unsafe { Cbc_setInteger(self.m, i.try_into().unwrap()) }
     }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Adds a column with the given coefficients in the existing rows.
+    /// This function is not available on libcbc < 2.10.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_col(
+        &mut self,
+        name: &CStr,
+        lower: f64,
+        upper: f64,
+        obj: f64,
+        is_integer: bool,
+        rows: &[c_int],
+        coeffs: &[f64],
+    ) {
+        assert_eq!(rows.len(), coeffs.len());
+        let num_rows = self.num_rows();
+        assert!(rows.iter().all(|&i| i >= 0 && (i as usize) < num_rows));
+        unsafe {
+            Cbc_addCol(
+                self.m,
+                name.as_ptr(),
+                lower,
+                upper,
+                obj,
+                is_integer as c_char,
+                rows.len().try_into().unwrap(),
+                rows.as_ptr() as *mut c_int,
+                coeffs.as_ptr() as *mut f64,
+            )
+        }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Adds a row `sum(coeffs[k] * x[cols[k]]) sense rhs`, the sense
+    /// being `b'L'`, `b'G'` or `b'E'`.
+    /// This function is not available on libcbc < 2.10.
+    pub fn add_row(
+        &mut self,
+        name: &CStr,
+        cols: &[c_int],
+        coeffs: &[f64],
+        sense: c_char,
+        rhs: f64,
+    ) {
+        assert_eq!(cols.len(), coeffs.len());
+        let num_cols = self.num_cols();
+        assert!(cols.iter().all(|&i| i >= 0 && (i as usize) < num_cols));
+        unsafe {
+            Cbc_addRow(
+                self.m,
+                name.as_ptr(),
+                cols.len().try_into().unwrap(),
+                cols.as_ptr(),
+                coeffs.as_ptr(),
+                sense,
+                rhs,
+            )
+        }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Deletes the given rows.
+    /// This function is not available on libcbc < 2.10.
+    pub fn delete_rows(&mut self, rows: &[c_int]) {
+        let num_rows = self.num_rows();
+        assert!(rows.iter().all(|&i| i >= 0 && (i as usize) < num_rows));
+        unsafe { Cbc_deleteRows(self.m, rows.len().try_into().unwrap(), rows.as_ptr()) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Deletes the given columns.
+    /// This function is not available on libcbc < 2.10.
+    pub fn delete_cols(&mut self, cols: &[c_int]) {
+        let num_cols = self.num_cols();
+        assert!(cols.iter().all(|&i| i >= 0 && (i as usize) < num_cols));
+        unsafe { Cbc_deleteCols(self.m, cols.len().try_into().unwrap(), cols.as_ptr()) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Number of non-zero coefficients of a row.
+    /// This function is not available on libcbc < 2.10.
+    pub fn row_nz(&self, i: usize) -> usize {
+        assert!(i < self.num_rows());
+        unsafe {
+            Cbc_getRowNz(self.m, i.try_into().unwrap())
+                .try_into()
+                .unwrap()
+        }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Columns of the non-zero coefficients of a row.
+    /// This function is not available on libcbc < 2.10.
+    pub fn row_indices(&self, i: usize) -> &[c_int] {
+        let size = self.row_nz(i);
+        unsafe {
+            std::slice::from_raw_parts(Cbc_getRowIndices(self.m, i.try_into().unwrap()), size)
+        }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Non-zero coefficients of a row.
+    /// This function is not available on libcbc < 2.10.
+    pub fn row_coeffs(&self, i: usize) -> &[f64] {
+        let size = self.row_nz(i);
+        unsafe { std::slice::from_raw_parts(Cbc_getRowCoeffs(self.m, i.try_into().unwrap()), size) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Right-hand side of a row.
+    /// This function is not available on libcbc < 2.10.
+    pub fn row_rhs(&self, i: usize) -> f64 {
+        assert!(i < self.num_rows());
+        unsafe { Cbc_getRowRHS(self.m, i.try_into().unwrap()) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sense of a row: `b'L'`, `b'G'`, `b'E'`, `b'R'` for a ranged
+    /// row, or `b'N'` for a free row.
+    /// This function is not available on libcbc < 2.10.
+    pub fn row_sense(&self, i: usize) -> c_char {
+        assert!(i < self.num_rows());
+        unsafe { Cbc_getRowSense(self.m, i.try_into().unwrap()) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Number of non-zero coefficients of a column.
+    /// This function is not available on libcbc < 2.10.
+    pub fn col_nz(&self, i: usize) -> usize {
+        assert!(i < self.num_cols());
+        unsafe {
+            Cbc_getColNz(self.m, i.try_into().unwrap())
+                .try_into()
+                .unwrap()
+        }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Rows of the non-zero coefficients of a column.
+    /// This function is not available on libcbc < 2.10.
+    pub fn col_indices(&self, i: usize) -> &[c_int] {
+        let size = self.col_nz(i);
+        unsafe {
+            std::slice::from_raw_parts(Cbc_getColIndices(self.m, i.try_into().unwrap()), size)
+        }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Non-zero coefficients of a column.
+    /// This function is not available on libcbc < 2.10.
+    pub fn col_coeffs(&self, i: usize) -> &[f64] {
+        let size = self.col_nz(i);
+        unsafe { std::slice::from_raw_parts(Cbc_getColCoeffs(self.m, i.try_into().unwrap()), size) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Number of integer columns.
+    /// This function is not available on libcbc < 2.10.
+    pub fn num_integers(&self) -> usize {
+        unsafe { Cbc_getNumIntegers(self.m).try_into().unwrap() }
+    }
     /// Adds multiple SOS constraints
     /// num_rows: the number of SOS constraints to add
     /// row_starts: The indices at which each new constraint starts in the col_indices array,
@@ -302,9 +778,267 @@ impl Model {
     pub fn set_parameter(&mut self, name: &CStr, value: &CStr) {
         unsafe { Cbc_setParameter(self.m, name.as_ptr(), value.as_ptr()) };
     }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets the time limit, in seconds.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_maximum_seconds(&mut self, value: f64) {
+        unsafe { Cbc_setMaximumSeconds(self.m, value) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Gets the time limit, in seconds.
+    /// This function is not available on libcbc < 2.10.
+    pub fn maximum_seconds(&self) -> f64 {
+        unsafe { Cbc_getMaximumSeconds(self.m) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets the limit on the number of nodes.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_maximum_nodes(&mut self, value: c_int) {
+        unsafe { Cbc_setMaximumNodes(self.m, value) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Gets the limit on the number of nodes.
+    /// This function is not available on libcbc < 2.10.
+    pub fn maximum_nodes(&self) -> c_int {
+        unsafe { Cbc_getMaximumNodes(self.m) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets the limit on the number of solutions.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_maximum_solutions(&mut self, value: c_int) {
+        unsafe { Cbc_setMaximumSolutions(self.m, value) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Gets the limit on the number of solutions.
+    /// This function is not available on libcbc < 2.10.
+    pub fn maximum_solutions(&self) -> c_int {
+        unsafe { Cbc_getMaximumSolutions(self.m) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets the log level.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_log_level(&mut self, value: c_int) {
+        unsafe { Cbc_setLogLevel(self.m, value) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Gets the log level.
+    /// This function is not available on libcbc < 2.10.
+    pub fn log_level(&self) -> c_int {
+        unsafe { Cbc_getLogLevel(self.m) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets the cutoff on the objective value.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_cutoff(&mut self, value: f64) {
+        unsafe { Cbc_setCutoff(self.m, value) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Gets the cutoff on the objective value.
+    /// This function is not available on libcbc < 2.10.
+    pub fn cutoff(&self) -> f64 {
+        unsafe { Cbc_getCutoff(self.m) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets the absolute gap at which the search stops.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_allowable_gap(&mut self, value: f64) {
+        unsafe { Cbc_setAllowableGap(self.m, value) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Gets the absolute gap at which the search stops.
+    /// This function is not available on libcbc < 2.10.
+    pub fn allowable_gap(&self) -> f64 {
+        unsafe { Cbc_getAllowableGap(self.m) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets the relative gap at which the search stops.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_allowable_fraction_gap(&mut self, value: f64) {
+        unsafe { Cbc_setAllowableFractionGap(self.m, value) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Gets the relative gap at which the search stops.
+    /// This function is not available on libcbc < 2.10.
+    pub fn allowable_fraction_gap(&self) -> f64 {
+        unsafe { Cbc_getAllowableFractionGap(self.m) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Sets the relative gap, in percent, at which the search stops.
+    /// This function is not available on libcbc < 2.10.
+    pub fn set_allowable_percentage_gap(&mut self, value: f64) {
+        unsafe { Cbc_setAllowablePercentageGap(self.m, value) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Gets the relative gap, in percent, at which the search stops.
+    /// This function is not available on libcbc < 2.10.
+    pub fn allowable_percentage_gap(&self) -> f64 {
+        unsafe { Cbc_getAllowablePercentageGap(self.m) }
+    }
+    /// Adds a cut generator called with the LP solution of the nodes
+    /// every `how_often` nodes, and also on the integer solutions if
+    /// `at_solution` is true.
+    ///
+    /// The callback is called while solving: it must not solve a
+    /// model itself. If it panics, it is not called anymore, and
+    /// `solve` resumes the panic once CBC returns.
+    ///
+    /// This function is only available on the CBC master branch: the
+    /// `Cbc_addCutCallback` of CBC 2.10 takes fewer arguments, and
+    /// is not bound. With the `dynamic-loading` feature, panics if the
+    /// loaded libcbc does not have the one of the master branch.
+    #[cfg(cbc_master)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    pub fn add_cut_callback<F>(
+        &mut self,
+        callback: F,
+        name: &CStr,
+        how_often: c_int,
+        at_solution: bool,
+    ) where
+        F: FnMut(&[f64], &mut RowCuts) + Send + 'static,
+    {
+        // The Cbc_addCutCallback of the master branch comes with
+        // Cbc_addLazyConstraint
+        #[cfg(feature = "dynamic-loading")]
+        assert!(
+            Self::is_available("Cbc_addLazyConstraint"),
+            "the loaded libcbc does not have the cut callbacks of the CBC master branch"
+        );
+        let callback = Arc::new(Mutex::new(CutCallbackData {
+            callback: Box::new(callback),
+            panic: None,
+        }));
+        unsafe {
+            Cbc_addCutCallback(
+                self.m,
+                Some(cut_callback_trampoline),
+                name.as_ptr(),
+                Arc::as_ptr(&callback) as *mut c_void,
+                how_often,
+                at_solution as c_char,
+            )
+        };
+        self.cut_callbacks.push(callback);
+    }
+    /// Sets the callback called on each new incumbent solution.
+    ///
+    /// The solutions are given by CBC with column names, which are
+    /// mapped to the columns of the model when the callback is set.
+    /// Panics if two columns have the same name at this time.
+    ///
+    /// If the callback panics, it is not called anymore, and `solve`
+    /// resumes the panic once CBC returns.
+    ///
+    /// This function is only available on the CBC master branch.
+    #[cfg(cbc_master)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    pub fn set_incumbent_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(f64, Vec<f64>) + Send + 'static,
+    {
+        let mut col_indices = HashMap::new();
+        for i in 0..self.num_cols() {
+            let name = self.col_name(i);
+            if let Some(j) = col_indices.insert(name, i) {
+                panic!("columns {} and {} have the same name", j, i);
+            }
+        }
+        let data = Arc::new(Mutex::new(IncumbentCallbackData {
+            num_cols: self.num_cols(),
+            col_indices,
+            callback: Box::new(callback),
+            panic: None,
+        }));
+        unsafe {
+            Cbc_addIncumbentCallback(
+                self.m,
+                Some(incumbent_callback_trampoline),
+                Arc::as_ptr(&data) as *mut c_void,
+            )
+        };
+        self.incumbent_callback = Some(data);
+    }
+    /// sense: 'L', 'G', 'E' or 'R'
+    /// This function is only available on the CBC master branch.
+    #[cfg(cbc_master)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    pub fn add_lazy_constraint(
+        &mut self,
+        indices: &[c_int],
+        elements: &[f64],
+        sense: c_char,
+        rhs: f64,
+    ) {
+        assert_eq!(indices.len(), elements.len());
+        assert!(indices.iter().all(|&i| (i as usize) < self.num_cols()));
+        unsafe {
+            Cbc_addLazyConstraint(
+                self.m,
+                indices.len().try_into().unwrap(),
+                indices.as_ptr() as *mut c_int,
+                elements.as_ptr() as *mut f64,
+                sense,
+                rhs,
+            )
+        }
+    }
+    /// Sets the branching priorities and directions of columns, given
+    /// as `(column, priority, direction)`. Lower priorities are
+    /// branched on first; the default priority is 1000.
+    ///
+    /// The C API has no such setter: the priorities are written to a
+    /// temporary file given to CBC with the `priorityIn` parameter.
+    pub fn set_branch_priorities(
+        &mut self,
+        priorities: &[(usize, c_int, Option<BranchDirection>)],
+    ) -> std::io::Result<()> {
+        assert!(priorities.iter().all(|p| p.0 < self.num_cols()));
+        let file = TempFile::create("coin_cbc_priorities", &priority_file_contents(priorities))?;
+        let path = file.0.to_str().and_then(|p| CString::new(p).ok());
+        let path = path.ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid temporary path")
+        })?;
+        self.set_parameter(&CString::new("priorityIn").unwrap(), &path);
+        self.priority_file = Some(Arc::new(file));
+        Ok(())
+    }
     // TODO: callback
     pub fn solve(&mut self) -> c_int {
-        lock(|| unsafe { Cbc_solve(self.m) })
+        let status = lock(|| unsafe { Cbc_solve(self.m) });
+        #[cfg(cbc_master)]
+        self.resume_callback_panic();
+        status
+    }
+    /// Resumes the first panic caught in a callback while solving.
+    #[cfg(cbc_master)]
+    fn resume_callback_panic(&self) {
+        let cut_panics = self
+            .cut_callbacks
+            .iter()
+            .map(|data| data.lock().unwrap_or_else(|e| e.into_inner()).panic.take());
+        let incumbent_panic = self
+            .incumbent_callback
+            .iter()
+            .map(|data| data.lock().unwrap_or_else(|e| e.into_inner()).panic.take());
+        if let Some(payload) = cut_panics.chain(incumbent_panic).flatten().next() {
+            panic::resume_unwind(payload);
+        }
     }
     pub fn sum_primal_infeasibilities(&self) -> f64 {
         unsafe { Cbc_sumPrimalInfeasibilities(self.m) }
@@ -349,10 +1083,10 @@ impl Model {
         unsafe { Cbc_isInitialSolveProvenPrimalInfeasible(self.m) != 0 }
     }
 
-    #[cfg(feature = "cbc-310")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
     /// Primal row solution
-    /// This function is not available on libcbc < 3.10
+    /// This function is not available on libcbc < 2.10
     pub fn row_activity(&self) -> &[f64] {
         unsafe { std::slice::from_raw_parts(Cbc_getRowActivity(self.m), self.num_rows()) }
     }
@@ -361,17 +1095,19 @@ impl Model {
     pub fn col_solution(&self) -> &[f64] {
         unsafe { std::slice::from_raw_parts(Cbc_getColSolution(self.m), self.num_cols()) }
     }
-    /* Disabled until available in the C API
-    /// Dual row solution
+
+    #[cfg(cbc_master)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    /// Dual row solution.
+    /// This function is only available on the CBC master branch.
     pub fn row_price(&self) -> &[f64] {
         unsafe { std::slice::from_raw_parts(Cbc_getRowPrice(self.m), self.num_rows()) }
     }
-     */
 
-    #[cfg(feature = "cbc-310")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
     /// Dual column solution.
-    /// This function is not available on libcbc < 3.10.
+    /// This function is not available on libcbc < 2.10.
     pub fn reduced_cost(&self) -> &[f64] {
         unsafe { std::slice::from_raw_parts(Cbc_getReducedCost(self.m), self.num_cols()) }
     }
@@ -381,6 +1117,30 @@ impl Model {
     pub fn best_possible_value(&self) -> f64 {
         unsafe { Cbc_getBestPossibleObjValue(self.m) }
     }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Number of solutions saved while solving.
+    /// This function is not available on libcbc < 2.10.
+    pub fn num_saved_solutions(&self) -> usize {
+        unsafe { Cbc_numberSavedSolutions(self.m).try_into().unwrap() }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Column values of the `i`-th saved solution, the best first.
+    /// This function is not available on libcbc < 2.10.
+    pub fn saved_solution(&self, i: usize) -> &[f64] {
+        assert!(i < self.num_saved_solutions());
+        let sol = unsafe { Cbc_savedSolution(self.m, i.try_into().unwrap()) };
+        unsafe { std::slice::from_raw_parts(sol, self.num_cols()) }
+    }
+    #[cfg(cbc_ge_2_10)]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbc-210")))]
+    /// Objective value of the `i`-th saved solution.
+    /// This function is not available on libcbc < 2.10.
+    pub fn saved_solution_obj(&self, i: usize) -> f64 {
+        assert!(i < self.num_saved_solutions());
+        unsafe { Cbc_savedSolutionObj(self.m, i.try_into().unwrap()) }
+    }
     pub fn print_solution(&self) {
         unsafe { Cbc_printSolution(self.m) }
     }
@@ -428,14 +1188,34 @@ impl Clone for Model {
     fn clone(&self) -> Self {
         Self {
             m: lock(|| unsafe { Cbc_clone(self.m) }),
+            priority_file: self.priority_file.clone(),
+            #[cfg(cbc_master)]
+            cut_callbacks: self.cut_callbacks.clone(),
+            #[cfg(cbc_master)]
+            incumbent_callback: self.incumbent_callback.clone(),
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec, clippy::legacy_numeric_constants)]
 mod test {
     use super::*;
 
+    #[test]
+    fn priority_file() {
+        let priorities = [(0, 1, Some(BranchDirection::Up)), (3, 1000, None)];
+        assert_eq!(
+            "number,priority,direction\n0,1,U\n3,1000,N\n",
+            priority_file_contents(&priorities)
+        );
+        let file = TempFile::create("coin_cbc_test", "contents").unwrap();
+        let path = file.0.clone();
+        assert_eq!("contents", std::fs::read_to_string(&path).unwrap());
+        drop(file);
+        assert!(!path.exists());
+    }
+
     #[test]
     fn knapsack() {
         let mut m = Model::new();
@@ -480,6 +1260,155 @@ mod test {
         assert!((sol[4] - 1.).abs() < 1e-6);
     }
 
+    #[cfg(cbc_ge_2_10)]
+    #[test]
+    fn incremental_knapsack() {
+        let mut m = Model::new();
+        let empty = CString::new("").unwrap();
+        m.add_row(
+            &CString::new("capacity").unwrap(),
+            &[],
+            &[],
+            b'L' as c_char,
+            10.,
+        );
+        for (&obj, &weight) in [5., 3., 2., 7., 4.].iter().zip(&[2., 8., 4., 2., 5.]) {
+            m.add_col(&empty, 0., 1., obj, true, &[0], &[weight]);
+        }
+        m.add_row(&empty, &[0, 1], &[1., 1.], b'E' as c_char, 2.);
+        assert_eq!(5, m.num_cols());
+        assert_eq!(5, m.num_integers());
+        assert_eq!(5, m.row_nz(0));
+        assert_eq!(&[0, 1, 2, 3, 4], m.row_indices(0));
+        assert_eq!(&[2., 8., 4., 2., 5.], m.row_coeffs(0));
+        assert_eq!(10., m.row_rhs(0));
+        assert_eq!(b'L' as c_char, m.row_sense(0));
+        assert_eq!(&[0, 1], m.col_indices(1));
+        assert_eq!(&[8., 1.], m.col_coeffs(1));
+        m.delete_rows(&[1]);
+        assert_eq!(1, m.num_rows());
+        m.set_obj_sense(Sense::Maximize);
+        m.set_log_level(0);
+        assert_eq!(0, m.log_level());
+        m.set_maximum_seconds(60.);
+        assert_eq!(60., m.maximum_seconds());
+        m.set_allowable_gap(0.);
+        assert_eq!(0., m.allowable_gap());
+        m.solve();
+        assert!(m.is_proven_optimal());
+        assert!((m.obj_value() - 16.).abs() < 1e-6);
+        assert!(m.num_saved_solutions() >= 1);
+        assert!((m.saved_solution_obj(0) - 16.).abs() < 1e-6);
+        assert_eq!(m.col_solution(), m.saved_solution(0));
+    }
+
+    #[cfg(cbc_ge_2_10)]
+    #[test]
+    fn lp_file_and_limits() {
+        let mut m = Model::new();
+        m.load_problem(
+            5,
+            1,
+            &[0, 1, 2, 3, 4, 5],
+            &[0, 0, 0, 0, 0],
+            &[2., 8., 4., 2., 5.],
+            Some(&[0., 0., 0., 0., 0.]),
+            Some(&[1., 1., 1., 1., 1.]),
+            Some(&[-5., -3., -2., -7., -4.]),
+            Some(&[-f64::INFINITY]),
+            Some(&[10.]),
+        );
+        for i in 0..5 {
+            m.set_integer(i);
+        }
+        let file =
+            TempFile(std::env::temp_dir().join(format!("coin_cbc_test_{}.lp", std::process::id())));
+        let path = CString::new(file.0.to_str().unwrap()).unwrap();
+        m.write_lp(&path);
+
+        let mut m = Model::new();
+        m.read_lp(&path);
+        assert_eq!(5, m.num_cols());
+        assert_eq!(1, m.num_rows());
+        assert_eq!(5, m.num_integers());
+        assert_eq!(10., m.row_rhs(0));
+        // Item 1 is not in the optimum
+        m.delete_cols(&[1]);
+        assert_eq!(4, m.num_cols());
+        let names: Vec<CString> = (0..4).map(|i| m.col_name(i)).collect();
+        let names: Vec<&CStr> = names.iter().map(|n| n.as_c_str()).collect();
+        m.set_mip_start(&names, &[1., 0., 1., 1.]);
+        m.set_log_level(0);
+        m.set_maximum_nodes(1000);
+        assert_eq!(1000, m.maximum_nodes());
+        m.set_maximum_solutions(10);
+        assert_eq!(10, m.maximum_solutions());
+        m.set_cutoff(-1.);
+        assert_eq!(-1., m.cutoff());
+        m.set_allowable_fraction_gap(0.);
+        assert_eq!(0., m.allowable_fraction_gap());
+        m.set_allowable_percentage_gap(0.);
+        assert_eq!(0., m.allowable_percentage_gap());
+        m.solve();
+        assert!(m.is_proven_optimal());
+        assert!((m.obj_value() + 16.).abs() < 1e-6);
+        for (value, expected) in m.col_solution().iter().zip(&[1., 0., 1., 1.]) {
+            assert!((value - expected).abs() < 1e-6);
+        }
+    }
+
+    #[cfg(cbc_master)]
+    #[test]
+    fn lazy_constraint() {
+        let mut m = Model::new();
+        m.load_problem(
+            5,
+            1,
+            &[0, 1, 2, 3, 4, 5],
+            &[0, 0, 0, 0, 0],
+            &[2., 8., 4., 2., 5.],
+            Some(&[0., 0., 0., 0., 0.]),
+            Some(&[1., 1., 1., 1., 1.]),
+            Some(&[5., 3., 2., 7., 4.]),
+            Some(&[-f64::INFINITY]),
+            Some(&[10.]),
+        );
+        for i in 0..5 {
+            m.set_integer(i);
+        }
+        m.set_obj_sense(Sense::Maximize);
+        // Items 0 and 3, in the optimum without it, exclude each other
+        m.add_lazy_constraint(&[0, 3], &[1., 1.], b'L' as c_char, 1.);
+        assert_eq!(1, m.num_rows());
+        m.set_parameter(&CString::new("log").unwrap(), &CString::new("0").unwrap());
+        m.solve();
+        assert!(m.is_proven_optimal());
+        assert!((m.obj_value() - 11.).abs() < 1e-6);
+        assert!(m.col_solution()[0] + m.col_solution()[3] <= 1. + 1e-6);
+    }
+
+    #[test]
+    fn names() {
+        let mut m = Model::new();
+        m.load_problem(
+            2,
+            1,
+            &[0, 1, 2],
+            &[0, 0],
+            &[1., 1.],
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let name = CString::new("capacity").unwrap();
+        m.set_row_name(0, &name);
+        m.set_col_name(1, &CString::new("y").unwrap());
+        assert_eq!(name, m.row_name(0));
+        assert_eq!(CString::new("y").unwrap(), m.col_name(1));
+    }
+
     #[test]
     fn big_row() {
         let mut m = Model::new();
@@ -0,0 +1,134 @@
+//! Semi-continuous and semi-integer columns.
+
+use crate::{Col, ConstraintSense, Model, FEASIBILITY_TOLERANCE};
+
+impl Model {
+    /// Adds a semi-continuous column, whose value is either 0 or in
+    /// `[lower, upper]`, and returns it.
+    ///
+    /// CBC has no native semi-continuous columns: if `upper` is
+    /// finite, the column is switched on by an auxiliary binary column
+    /// with the rows `x <= upper z` and `x >= lower z`. Otherwise, an
+    /// auxiliary column `s` in `[0, lower]` is added with the row
+    /// `x + s >= lower`, `x` and `s` being in a SOS1 constraint.
+    ///
+    /// When solving, a value of the column below `lower` is rounded
+    /// to exactly 0 in the `Solution`.
+    ///
+    /// `lower` must be positive, and not greater than `upper`.
+    pub fn add_semicontinuous(&mut self, lower: f64, upper: f64) -> Col {
+        assert!(
+            0. < lower && lower <= upper,
+            "the bounds of a semi-continuous column must satisfy 0 < lower <= upper"
+        );
+        let x = self.add_col();
+        self.set_col_upper(x, upper);
+        let aux = if upper.is_finite() {
+            let z = self.add_binary();
+            self.add_constraint(vec![(x, 1.), (z, -upper)], ConstraintSense::LessOrEqual, 0.);
+            self.add_constraint(
+                vec![(x, 1.), (z, -lower)],
+                ConstraintSense::GreaterOrEqual,
+                0.,
+            );
+            z
+        } else {
+            let s = self.add_col();
+            self.set_col_upper(s, lower);
+            self.add_constraint(
+                vec![(x, 1.), (s, 1.)],
+                ConstraintSense::GreaterOrEqual,
+                lower,
+            );
+            self.add_sos1(vec![(x, 1.), (s, 2.)]);
+            s
+        };
+        self.semicontinuous.insert(x, (lower, aux));
+        x
+    }
+    /// Adds a semi-integer column, whose value is either 0 or an
+    /// integer in `[lower, upper]`, and returns it.
+    ///
+    /// See `add_semicontinuous` for the formulation.
+    pub fn add_semiinteger(&mut self, lower: f64, upper: f64) -> Col {
+        let x = self.add_semicontinuous(lower, upper);
+        self.set_integer(x);
+        x
+    }
+    /// Returns whether the column was added with `add_semicontinuous`
+    /// or `add_semiinteger`.
+    pub fn is_semicontinuous(&self, col: Col) -> bool {
+        self.semicontinuous.contains_key(&col)
+    }
+    /// Gets the auxiliary column of a semi-continuous column: the
+    /// binary column switching it on, or the SOS1 complement column
+    /// if its upper bound is infinite.
+    pub fn semicontinuous_aux(&self, col: Col) -> Option<Col> {
+        self.semicontinuous.get(&col).map(|&(_, aux)| aux)
+    }
+    /// Rounds to 0 the values of the semi-continuous columns that are
+    /// below their lower bound, up to the solver tolerances.
+    pub(crate) fn snap_semicontinuous(&self, col_solution: &mut [f64]) {
+        for (col, &(lower, _)) in &self.semicontinuous {
+            let value = &mut col_solution[col.as_usize()];
+            if *value < lower - FEASIBILITY_TOLERANCE {
+                *value = 0.;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Sense;
+
+    /// Minimum lot sizes: produce at least 10 units from two lines,
+    /// each producing 0 or within [lot, capacity].
+    fn min_lot(capacity: f64) -> (f64, f64) {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let cheap = m.add_semicontinuous(6., capacity);
+        let expensive = m.add_semicontinuous(5., capacity);
+        assert!(m.is_semicontinuous(cheap));
+        assert!(m.semicontinuous_aux(expensive).is_some());
+        m.add_constraint(
+            vec![(cheap, 1.), (expensive, 1.)],
+            ConstraintSense::GreaterOrEqual,
+            10.,
+        );
+        m.set_obj_coeff(cheap, 1.);
+        m.set_obj_coeff(expensive, 3.);
+        m.set_obj_sense(Sense::Minimize);
+        let sol = m.solve();
+        assert!(sol.raw().is_proven_optimal());
+        (sol.col(cheap), sol.col(expensive))
+    }
+
+    #[test]
+    fn binary_formulation() {
+        assert_eq!((10., 0.), min_lot(20.));
+        // The cheap line cannot produce everything, and the expensive
+        // one must produce at least its lot size
+        assert_eq!((6., 5.), min_lot(8.));
+    }
+
+    #[test]
+    fn sos1_formulation() {
+        assert_eq!((10., 0.), min_lot(f64::INFINITY));
+    }
+
+    #[test]
+    fn semiinteger() {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_semiinteger(2.5, 10.);
+        assert!(m.is_integer[x.as_usize()]);
+        let row = m.add_constraint(vec![(x, 1.)], ConstraintSense::GreaterOrEqual, 0.5);
+        m.set_obj_coeff(x, 1.);
+        m.set_obj_sense(Sense::Minimize);
+        assert_eq!(3., m.solve().col(x));
+        m.set_row_lower(row, 0.);
+        assert_eq!(0., m.solve().col(x));
+    }
+}
@@ -0,0 +1,178 @@
+//! Cutting-plane loop with a user separation oracle.
+
+use crate::{Col, ConstraintSense, Model, Row, Solution};
+
+/// A linear constraint `expr sense rhs` to add to a model, e.g. found
+/// by a separation oracle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cut {
+    expr: Vec<(Col, f64)>,
+    sense: ConstraintSense,
+    rhs: f64,
+}
+
+impl Cut {
+    /// Creates the constraint `expr sense rhs`.
+    pub fn new<I: IntoIterator<Item = (Col, f64)>>(
+        expr: I,
+        sense: ConstraintSense,
+        rhs: f64,
+    ) -> Self {
+        Cut {
+            expr: expr.into_iter().collect(),
+            sense,
+            rhs,
+        }
+    }
+    /// Gets the linear expression of the constraint.
+    pub fn expr(&self) -> &[(Col, f64)] {
+        &self.expr
+    }
+    /// Gets the sense of the constraint.
+    pub fn sense(&self) -> ConstraintSense {
+        self.sense
+    }
+    /// Gets the right-hand side of the constraint.
+    pub fn rhs(&self) -> f64 {
+        self.rhs
+    }
+    /// Gets by how much the given column values violate the
+    /// constraint, or 0 if they satisfy it.
+    pub fn violation(&self, values: &[f64]) -> f64 {
+        let activity: f64 = self
+            .expr
+            .iter()
+            .map(|&(col, coeff)| coeff * values[col.as_usize()])
+            .sum();
+        match self.sense {
+            ConstraintSense::LessOrEqual => (activity - self.rhs).max(0.),
+            ConstraintSense::GreaterOrEqual => (self.rhs - activity).max(0.),
+            ConstraintSense::Equal => (activity - self.rhs).abs(),
+        }
+    }
+}
+
+impl Model {
+    /// Adds the cut as a new row, and returns it.
+    pub fn add_cut(&mut self, cut: &Cut) -> Row {
+        self.add_constraint(cut.expr.iter().copied(), cut.sense, cut.rhs)
+    }
+    /// Solves the model, and repeatedly adds the cuts returned by the
+    /// oracle for the current solution and solves again, until the
+    /// oracle returns no cut or `max_rounds` rounds of cuts were added.
+    /// Returns the last solution.
+    ///
+    /// The cuts stay in the model as rows. The initial solutions of
+    /// the model are only used by the first solve, as they may be cut
+    /// off, and are restored at the end. Each next solve instead gets
+    /// the previous solution as initial solution, which is discarded
+    /// if it violates the new cuts (see `InitialSolutionStatus`). The
+    /// loop also stops if a solve is not proven optimal.
+    pub fn solve_with_separation<F>(&mut self, max_rounds: usize, mut oracle: F) -> Solution
+    where
+        F: FnMut(&Solution) -> Vec<Cut>,
+    {
+        let mut solution = self.solve();
+        let initial_solutions = std::mem::take(&mut self.initial_solutions);
+        for _ in 0..max_rounds {
+            if !solution.raw().is_proven_optimal() {
+                break;
+            }
+            let cuts = oracle(&solution);
+            if cuts.is_empty() {
+                break;
+            }
+            let previous: Vec<f64> = self.cols().map(|col| solution.col(col)).collect();
+            for cut in &cuts {
+                self.add_cut(cut);
+            }
+            self.initial_solutions.clear();
+            self.add_initial_solution(self.cols().zip(previous));
+            solution = self.solve();
+        }
+        self.initial_solutions = initial_solutions;
+        solution
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{InitialSolutionStatus, Sense};
+
+    fn model() -> (Model, Col, Col) {
+        let mut m = Model::default();
+        m.set_parameter("log", "0");
+        let x = m.add_integer();
+        m.set_col_upper(x, 10.);
+        let y = m.add_integer();
+        m.set_col_upper(y, 10.);
+        m.set_obj_coeff(x, 2.);
+        m.set_obj_coeff(y, 1.);
+        m.set_obj_sense(Sense::Maximize);
+        (m, x, y)
+    }
+
+    #[test]
+    fn violation() {
+        let (_, x, y) = model();
+        let cut = Cut::new(vec![(x, 1.), (y, 1.)], ConstraintSense::LessOrEqual, 5.);
+        assert_eq!(2., cut.violation(&[3., 4.]));
+        assert_eq!(0., cut.violation(&[3., 2.]));
+    }
+
+    #[test]
+    fn separation() {
+        let (mut m, x, y) = model();
+        m.add_initial_solution(vec![(x, 1.), (y, 1.)]);
+        let num_rows = m.num_rows();
+        let mut rounds = 0;
+        // Maximize 2x + y s.t. x + y <= 5 and x <= 3, only known by the oracle
+        let sol = m.solve_with_separation(10, |sol| {
+            rounds += 1;
+            let values = [sol.col(x), sol.col(y)];
+            let cuts = vec![
+                Cut::new(vec![(x, 1.), (y, 1.)], ConstraintSense::LessOrEqual, 5.),
+                Cut::new(vec![(x, 1.)], ConstraintSense::LessOrEqual, 3.),
+            ];
+            cuts.into_iter()
+                .filter(|c| c.violation(&values) > 1e-6)
+                .collect()
+        });
+        assert_eq!(3., sol.col(x));
+        assert_eq!(2., sol.col(y));
+        assert!(rounds >= 2);
+        assert!(m.num_rows() > num_rows);
+        // The initial solution of the caller is kept
+        assert_eq!(1, m.num_initial_solutions());
+
+        let (mut m, x, _) = model();
+        let sol = m.solve_with_separation(0, |_| unreachable!());
+        assert_eq!(10., sol.col(x));
+    }
+
+    #[test]
+    fn previous_solution_as_start() {
+        let (mut m, x, y) = model();
+        let mut rounds = 0;
+        // A cut that the solution satisfies, then none
+        let sol = m.solve_with_separation(10, |_| {
+            rounds += 1;
+            match rounds {
+                1 => vec![Cut::new(
+                    vec![(x, 1.), (y, 1.)],
+                    ConstraintSense::LessOrEqual,
+                    100.,
+                )],
+                _ => vec![],
+            }
+        });
+        assert_eq!(2, rounds);
+        assert_eq!(
+            InitialSolutionStatus::Feasible,
+            sol.initial_solution_status()
+        );
+        assert_eq!(30., sol.raw().obj_value());
+        assert_eq!(0, m.num_initial_solutions());
+    }
+}
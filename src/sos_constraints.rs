@@ -31,6 +31,13 @@ impl SOSConstraints {
     pub fn is_empty(&self) -> bool {
         self.row_starts.len() <= 1
     }
+    /// Iterates over the constraints, as (column indices, weights) pairs
+    pub fn iter(&self) -> impl Iterator<Item = (&[c_int], &[f64])> {
+        self.row_starts.windows(2).map(move |w| {
+            let range = w[0] as usize..w[1] as usize;
+            (&self.col_indices[range.clone()], &self.weights[range])
+        })
+    }
     pub fn add_to_raw(&self, raw: &mut crate::raw::Model, sos_type: SOSConstraintType) {
         if !self.is_empty() {
             raw.add_sos(&self.row_starts, &self.col_indices, &self.weights, sos_type);